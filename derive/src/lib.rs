@@ -0,0 +1,160 @@
+//! Derive macros for the `technical_analysis` crate. Enable them with the `derive` feature of
+//! `technical_analysis` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident, Path};
+
+/// the accessor traits, the method each one needs, and the type it returns
+const ACCESSORS: [(&str, &str); 6] = [
+    ("timestamp", "i64"),
+    ("open", "f64"),
+    ("high", "f64"),
+    ("low", "f64"),
+    ("close", "f64"),
+    ("volume", "f64"),
+];
+
+/// Implements `Timestamp`, `Open`, `High`, `Low`, `Close` and `Volume` for a struct with named
+/// fields. Each accessor reads the field marked with the matching `#[ta(...)]` attribute, or
+/// otherwise the unmarked field of the same name. Accessors with no field are left
+/// unimplemented.
+///
+/// Fields are converted with `From`, so only types that convert without loss compile: `f32` and
+/// integers up to 32 bits for prices and volume, and signed integers up to 64 bits or unsigned
+/// ones up to 32 bits for the timestamp. Convert `u64` or `usize` fields yourself.
+///
+/// The impls name the traits through `::technical_analysis`. Where the crate is reachable under
+/// another path, such as a re-export or inside `technical_analysis` itself, give that path with
+/// `#[ta(crate = ...)]` on the struct.
+///
+/// ```ignore
+/// #[derive(Ohlcv)]
+/// #[ta(crate = my_reexports::technical_analysis)]
+/// struct Bar {
+///     #[ta(timestamp)]
+///     time: i64,
+///     #[ta(high)]
+///     h: f32,
+///     #[ta(low)]
+///     l: f32,
+///     close: f64,
+///     #[ta(volume)]
+///     size: u32,
+/// }
+/// ```
+#[proc_macro_derive(Ohlcv, attributes(ta))]
+pub fn derive_ohlcv(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Ohlcv can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Ohlcv can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut krate: Path = syn::parse_quote!(::technical_analysis);
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("ta")) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("crate") {
+                return Err(meta.error(
+                    "expected `crate = ...` on the struct, accessors are marked on fields",
+                ));
+            }
+            krate = meta.value()?.parse()?;
+            Ok(())
+        })?;
+    }
+
+    // the field marked for each accessor, in the order of `ACCESSORS`
+    let mut marked: [Option<&syn::Field>; 6] = [None; 6];
+    for field in fields {
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("ta")) {
+            attr.parse_nested_meta(|meta| {
+                let position = ACCESSORS
+                    .iter()
+                    .position(|(name, _)| meta.path.is_ident(name))
+                    .ok_or_else(|| {
+                        meta.error(
+                            "expected one of `timestamp`, `open`, `high`, `low`, `close` or `volume`",
+                        )
+                    })?;
+                if marked[position].replace(field).is_some() {
+                    return Err(meta.error(format!(
+                        "more than one field is marked `{}`",
+                        ACCESSORS[position].0
+                    )));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut impls = Vec::new();
+    for ((accessor, output), marked) in ACCESSORS.iter().zip(marked) {
+        // fall back to an unmarked field of the same name
+        let field = marked.or_else(|| {
+            fields
+                .iter()
+                .filter(|field| !field.attrs.iter().any(|attr| attr.path().is_ident("ta")))
+                .find(|field| field.ident.as_ref().is_some_and(|ident| ident == accessor))
+        });
+        let Some(field) = field else {
+            continue;
+        };
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let method = Ident::new(accessor, proc_macro2::Span::call_site());
+        let trait_name = Ident::new(&capitalize(accessor), proc_macro2::Span::call_site());
+        let output = Ident::new(output, proc_macro2::Span::call_site());
+        // spanned to the field type, so a lossy conversion is reported on the field
+        let converted = Ident::new(&output.to_string(), ty.span());
+        let value = quote_spanned! {ty.span()=>
+            <#converted as ::core::convert::From<#ty>>::from(self.#ident)
+        };
+        impls.push(quote! {
+            impl #impl_generics #krate::#trait_name for #name #ty_generics #where_clause {
+                #[allow(clippy::useless_conversion)]
+                fn #method(&self) -> #output {
+                    #value
+                }
+            }
+        });
+    }
+
+    if impls.is_empty() {
+        return Err(Error::new_spanned(
+            name,
+            "no field is named or marked `timestamp`, `open`, `high`, `low`, `close` or `volume`",
+        ));
+    }
+    Ok(quote! { #(#impls)* })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
@@ -0,0 +1,369 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+
+/// when a `BarBuilder` completes a bar
+#[derive(Clone, Debug, PartialEq)]
+pub enum BarKind {
+    /// a bar for every interval of this many nanoseconds that has trades
+    Time(i64),
+    /// a bar every this many trades
+    Tick(u64),
+    /// a bar once the traded size reaches this amount
+    Volume(f64),
+    /// a bar once the traded value, price times size, reaches this amount
+    Dollar(f64),
+}
+
+/// Trading sessions start every `period` nanoseconds, `offset` nanoseconds after the Unix epoch.
+/// A daily session opening at 14:30 UTC has a period of one day and an offset of 14.5 hours
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    pub period: i64,
+    pub offset: i64,
+}
+
+impl Session {
+    fn start(&self, timestamp: i64) -> i64 {
+        timestamp - (timestamp - self.offset).rem_euclid(self.period)
+    }
+}
+
+pub struct BarBuilderFactory {
+    kind: BarKind,
+    session: Option<Session>,
+}
+
+/// Aggregates trades into `Candle`s. Each trade's `close()` is its price and its `volume()` its
+/// size. `next` returns the bars the trade completes, oldest first:
+///
+/// - time bars complete on the first trade of a later interval, which starts the next bar.
+///   Intervals without trades produce no bar. Bars are aligned to the session start, or to the
+///   Unix epoch without a session
+/// - tick, volume and dollar bars complete on the trade that reaches the threshold, which is
+///   included in full rather than split across bars
+///
+/// With a session, no bar spans two sessions: the first trade of a new session completes the
+/// partial bar of the previous one, so a single trade can complete two bars. Completed candles
+/// are timestamped with the start of their interval for time bars and with their first trade
+/// otherwise, and carry the trade count and VWAP. Trades older than the partial bar are added to
+/// it
+#[derive(Clone, Debug)]
+pub struct BarBuilder {
+    kind: BarKind,
+    session: Option<Session>,
+    partial: Option<Candle>,
+    // the bar completes when a trade reaches this timestamp
+    end: Option<i64>,
+    value: f64,
+}
+
+impl BarBuilderFactory {
+    pub fn new() -> Self {
+        Self {
+            kind: BarKind::Time(60_000_000_000),
+            session: None,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: BarKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_session(mut self, session: Session) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    pub fn build(self) -> Result<BarBuilder, Error> {
+        let valid = match self.kind {
+            BarKind::Time(interval) => interval > 0,
+            BarKind::Tick(count) => count > 0,
+            BarKind::Volume(threshold) | BarKind::Dollar(threshold) => threshold > 0.0,
+        };
+        if !valid {
+            return Err(anyhow!(
+                "Bar size must be positive. You used {:?}",
+                self.kind
+            ));
+        }
+        if let Some(session) = &self.session {
+            if session.period <= 0 {
+                return Err(anyhow!(
+                    "Session period must be positive. You used {}",
+                    session.period
+                ));
+            }
+        }
+        Ok(BarBuilder {
+            kind: self.kind,
+            session: self.session,
+            partial: None,
+            end: None,
+            value: 0.0,
+        })
+    }
+}
+
+impl<T: Close + Volume + Timestamp> Indicator<T> for BarBuilder {
+    type Output = Vec<Candle>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let (timestamp, price, size) = (next.timestamp(), next.close(), next.volume());
+        let mut completed: Vec<Candle> = self.advance_to(timestamp).into_iter().collect();
+
+        let bar = match &mut self.partial {
+            Some(bar) => bar,
+            None => {
+                self.start(timestamp, price);
+                self.partial.as_mut().unwrap()
+            }
+        };
+        bar.high = bar.high.max(price);
+        bar.low = bar.low.min(price);
+        bar.close = price;
+        bar.volume += size;
+        bar.trade_count = bar.trade_count.map(|count| count + 1);
+        self.value += price * size;
+
+        let full = match self.kind {
+            BarKind::Time(_) => false,
+            BarKind::Tick(count) => bar.trade_count >= Some(count),
+            BarKind::Volume(threshold) => bar.volume >= threshold,
+            BarKind::Dollar(threshold) => self.value >= threshold,
+        };
+        if full {
+            completed.extend(self.flush());
+        }
+        completed
+    }
+}
+
+impl BarBuilder {
+    pub fn factory() -> BarBuilderFactory {
+        BarBuilderFactory::new()
+    }
+
+    /// the bar in progress, if it has any trades. Its VWAP is filled in when it completes
+    pub fn partial(&self) -> Option<&Candle> {
+        self.partial.as_ref()
+    }
+
+    /// completes the partial bar early, for example at the end of the data
+    pub fn flush(&mut self) -> Option<Candle> {
+        let mut bar = self.partial.take()?;
+        self.end = None;
+        if bar.volume > 0.0 {
+            bar.vwap = Some((self.value / bar.volume).clamp(bar.low, bar.high));
+        }
+        self.value = 0.0;
+        Some(bar)
+    }
+
+    /// completes the partial bar if it ends by `timestamp`, so a time bar can be emitted without
+    /// waiting for the next trade
+    pub fn advance_to(&mut self, timestamp: i64) -> Option<Candle> {
+        match self.end {
+            Some(end) if timestamp >= end => self.flush(),
+            _ => None,
+        }
+    }
+
+    fn start(&mut self, timestamp: i64, price: f64) {
+        let session = self.session.as_ref().map(|session| {
+            let start = session.start(timestamp);
+            (start, start + session.period)
+        });
+        let (start, end) = match self.kind {
+            BarKind::Time(interval) => {
+                let origin = session.map_or(0, |(session_start, _)| session_start);
+                let start = timestamp - (timestamp - origin).rem_euclid(interval);
+                let end = start + interval;
+                (
+                    start,
+                    Some(session.map_or(end, |(_, session_end)| end.min(session_end))),
+                )
+            }
+            _ => (timestamp, session.map(|(_, session_end)| session_end)),
+        };
+        self.end = end;
+        self.partial = Some(Candle {
+            timestamp: start,
+            open: price,
+            close: price,
+            high: price,
+            low: price,
+            volume: 0.0,
+            trade_count: Some(0),
+            vwap: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bars::*, *};
+
+    const SECOND: i64 = 1_000_000_000;
+
+    fn bar(
+        timestamp: i64,
+        [open, close, high, low]: [f64; 4],
+        volume: f64,
+        trade_count: u64,
+        vwap: f64,
+    ) -> Candle {
+        Candle::new(open, close, high, low, volume)
+            .unwrap()
+            .with_timestamp(timestamp)
+            .with_trade_count(trade_count)
+            .with_vwap(vwap)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        BarBuilder::factory()
+            .with_kind(BarKind::Dollar(1_000_000.0))
+            .with_session(Session {
+                period: 86_400 * SECOND,
+                offset: 52_200 * SECOND,
+            })
+            .build()
+            .unwrap();
+        assert!(BarBuilder::factory()
+            .with_kind(BarKind::Time(0))
+            .build()
+            .is_err());
+        assert!(BarBuilder::factory()
+            .with_kind(BarKind::Tick(0))
+            .build()
+            .is_err());
+        assert!(BarBuilder::factory()
+            .with_kind(BarKind::Volume(-1.0))
+            .build()
+            .is_err());
+        assert!(BarBuilder::factory()
+            .with_session(Session {
+                period: 0,
+                offset: 0
+            })
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        let mut builder = BarBuilder::factory()
+            .with_kind(BarKind::Time(60 * SECOND))
+            .build()
+            .unwrap();
+
+        assert_eq!(builder.next(Trade::new(61 * SECOND, 10.0, 1.0)), []);
+        assert_eq!(builder.next(Trade::new(70 * SECOND, 12.0, 3.0)), []);
+        assert_eq!(builder.next(Trade::new(119 * SECOND, 11.0, 1.0)), []);
+        assert_eq!(builder.partial().unwrap().close, 11.0);
+        // the minute from 180s has no trades, so has no bar
+        assert_eq!(
+            builder.next(Trade::new(245 * SECOND, 9.0, 2.0)),
+            [bar(60 * SECOND, [10.0, 11.0, 12.0, 10.0], 5.0, 3, 11.4)]
+        );
+        assert_eq!(builder.advance_to(299 * SECOND), None);
+        assert_eq!(
+            builder.advance_to(300 * SECOND),
+            Some(bar(240 * SECOND, [9.0, 9.0, 9.0, 9.0], 2.0, 1, 9.0))
+        );
+        assert_eq!(builder.partial(), None);
+        assert_eq!(builder.flush(), None);
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let trades = [
+            Trade::new(1, 10.0, 4.0),
+            Trade::new(2, 11.0, 4.0),
+            Trade::new(3, 12.0, 4.0),
+            Trade::new(4, 9.0, 1.0),
+        ];
+
+        let mut tick = BarBuilder::factory()
+            .with_kind(BarKind::Tick(2))
+            .build()
+            .unwrap();
+        let mut volume = BarBuilder::factory()
+            .with_kind(BarKind::Volume(10.0))
+            .build()
+            .unwrap();
+        let mut dollar = BarBuilder::factory()
+            .with_kind(BarKind::Dollar(40.0))
+            .build()
+            .unwrap();
+        let mut ticks = Vec::new();
+        let mut volumes = Vec::new();
+        let mut dollars = Vec::new();
+        for trade in trades {
+            ticks.extend(tick.next(trade.clone()));
+            volumes.extend(volume.next(trade.clone()));
+            dollars.extend(dollar.next(trade));
+        }
+
+        assert_eq!(
+            ticks,
+            [
+                bar(1, [10.0, 11.0, 11.0, 10.0], 8.0, 2, 10.5),
+                bar(3, [12.0, 9.0, 12.0, 9.0], 5.0, 2, 11.4),
+            ]
+        );
+        assert_eq!(volumes, [bar(1, [10.0, 12.0, 12.0, 10.0], 12.0, 3, 11.0)]);
+        assert_eq!(volume.partial().unwrap().volume, 1.0);
+        assert_eq!(
+            dollars,
+            [
+                bar(1, [10.0, 10.0, 10.0, 10.0], 4.0, 1, 10.0),
+                bar(2, [11.0, 11.0, 11.0, 11.0], 4.0, 1, 11.0),
+                bar(3, [12.0, 12.0, 12.0, 12.0], 4.0, 1, 12.0),
+            ]
+        );
+        assert_eq!(
+            dollar.flush(),
+            Some(bar(4, [9.0, 9.0, 9.0, 9.0], 1.0, 1, 9.0))
+        );
+    }
+
+    #[test]
+    fn test_session() {
+        // sessions open at 30s past each 100s, so minute bars are cut short at the session end
+        let session = Session {
+            period: 100 * SECOND,
+            offset: 30 * SECOND,
+        };
+        let mut time = BarBuilder::factory()
+            .with_kind(BarKind::Time(60 * SECOND))
+            .with_session(session.clone())
+            .build()
+            .unwrap();
+        assert_eq!(time.next(Trade::new(95 * SECOND, 10.0, 1.0)), []);
+        assert_eq!(time.partial().unwrap().timestamp, 90 * SECOND);
+        assert_eq!(
+            time.next(Trade::new(130 * SECOND, 11.0, 1.0)),
+            [bar(90 * SECOND, [10.0, 10.0, 10.0, 10.0], 1.0, 1, 10.0)]
+        );
+        assert_eq!(time.partial().unwrap().timestamp, 130 * SECOND);
+
+        let mut volume = BarBuilder::factory()
+            .with_kind(BarKind::Volume(3.0))
+            .with_session(session)
+            .build()
+            .unwrap();
+        assert_eq!(volume.next(Trade::new(110 * SECOND, 10.0, 1.0)), []);
+        assert_eq!(volume.next(Trade::new(120 * SECOND, 12.0, 1.0)), []);
+        // completes the last bar of the session, then a full bar of its own
+        assert_eq!(
+            volume.next(Trade::new(130 * SECOND, 11.0, 5.0)),
+            [
+                bar(110 * SECOND, [10.0, 12.0, 12.0, 10.0], 2.0, 2, 11.0),
+                bar(130 * SECOND, [11.0, 11.0, 11.0, 11.0], 5.0, 1, 11.0),
+            ]
+        );
+        assert_eq!(volume.partial(), None);
+    }
+}
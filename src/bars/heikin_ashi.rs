@@ -0,0 +1,194 @@
+use crate::*;
+use anyhow::Error;
+
+pub struct HeikinAshiFactory {}
+
+/// Transforms bars into Heikin-Ashi candles, which average out noise so trends show as runs of
+/// candles of one colour. The close is the mean of the bar's prices, the open the midpoint of
+/// the previous Heikin-Ashi candle's body, and the high and low extend to cover both. The output
+/// can be fed to any other indicator. Volume and timestamp are zero, as the inputs are only
+/// required to have prices; use `next_candle` to carry them over, for example into a `Resampler`
+#[derive(Clone, Debug)]
+pub struct HeikinAshi {
+    prev: Option<(f64, f64)>,
+}
+
+impl HeikinAshiFactory {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn build(self) -> Result<HeikinAshi, Error> {
+        Ok(HeikinAshi { prev: None })
+    }
+}
+
+impl<T: Open + High + Low + Close> Indicator<T> for HeikinAshi {
+    type Output = Candle;
+    fn next(&mut self, next: T) -> Self::Output {
+        let close = (next.open() + next.high() + next.low() + next.close()) / 4.0;
+        let open = match self.prev {
+            Some((prev_open, prev_close)) => (prev_open + prev_close) / 2.0,
+            None => (next.open() + next.close()) / 2.0,
+        };
+        self.prev = Some((open, close));
+        Candle {
+            timestamp: 0,
+            open,
+            close,
+            high: next.high().max(open).max(close),
+            low: next.low().min(open).min(close),
+            volume: 0.0,
+            trade_count: None,
+            vwap: None,
+        }
+    }
+}
+
+impl HeikinAshi {
+    pub fn factory() -> HeikinAshiFactory {
+        HeikinAshiFactory::new()
+    }
+
+    /// like `next`, but the candle keeps the input's timestamp and volume
+    pub fn next_candle<T: Timestamp + Open + High + Low + Close + Volume>(
+        &mut self,
+        next: T,
+    ) -> Candle {
+        Candle {
+            timestamp: next.timestamp(),
+            volume: next.volume(),
+            ..self.next(next)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bars::{HeikinAshi, Resampler, Timeframe},
+        indicators::PSAR,
+        *,
+    };
+
+    #[test]
+    fn test_build() {
+        HeikinAshi::factory().build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut heikin_ashi = HeikinAshi::factory().build().unwrap();
+
+        // inputs only need prices
+        struct OHLC(f64, f64, f64, f64);
+        impl Open for OHLC {
+            fn open(&self) -> f64 {
+                self.0
+            }
+        }
+        impl High for OHLC {
+            fn high(&self) -> f64 {
+                self.1
+            }
+        }
+        impl Low for OHLC {
+            fn low(&self) -> f64 {
+                self.2
+            }
+        }
+        impl Close for OHLC {
+            fn close(&self) -> f64 {
+                self.3
+            }
+        }
+
+        assert_eq!(
+            heikin_ashi.next(OHLC(10.0, 13.0, 9.0, 12.0)),
+            Candle::new(11.0, 11.0, 13.0, 9.0, 0.0).unwrap()
+        );
+        assert_eq!(
+            heikin_ashi.next(
+                Candle::new(12.0, 14.0, 15.0, 11.5, 150.0)
+                    .unwrap()
+                    .with_timestamp(60)
+            ),
+            Candle::new(11.0, 13.125, 15.0, 11.0, 0.0).unwrap()
+        );
+        // a gap down still opens within the previous body
+        assert_eq!(
+            heikin_ashi.next_candle(
+                Candle::new(8.0, 7.0, 8.5, 6.5, 100.0)
+                    .unwrap()
+                    .with_timestamp(120)
+            ),
+            Candle::new(12.0625, 7.5, 12.0625, 6.5, 100.0)
+                .unwrap()
+                .with_timestamp(120)
+        );
+    }
+
+    #[test]
+    fn test_chain() {
+        let mut heikin_ashi = HeikinAshi::factory().build().unwrap();
+        let mut psar = PSAR::factory().build().unwrap();
+        let mut reference = PSAR::factory().build().unwrap();
+
+        let bars = [
+            (10.0, 12.0, 13.0, 9.0),
+            (12.0, 14.0, 15.0, 11.5),
+            (8.0, 7.0, 8.5, 6.5),
+        ];
+        let smoothed = [
+            (11.0, 11.0, 13.0, 9.0),
+            (11.0, 13.125, 15.0, 11.0),
+            (12.0625, 7.5, 12.0625, 6.5),
+        ];
+        for ((open, close, high, low), (ha_open, ha_close, ha_high, ha_low)) in
+            bars.into_iter().zip(smoothed)
+        {
+            let candle = Candle::new(open, close, high, low, 0.0).unwrap();
+            assert_eq!(
+                psar.next(heikin_ashi.next(candle)),
+                reference.next(Candle::new(ha_open, ha_close, ha_high, ha_low, 0.0).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_resample() {
+        const MINUTE: i64 = 60_000_000_000;
+        let mut heikin_ashi = HeikinAshi::factory().build().unwrap();
+        let mut resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(2 * MINUTE))
+            .build()
+            .unwrap();
+
+        let bars = [
+            Candle::new(10.0, 12.0, 13.0, 9.0, 100.0).unwrap(),
+            Candle::new(12.0, 14.0, 15.0, 11.5, 150.0)
+                .unwrap()
+                .with_timestamp(MINUTE),
+            Candle::new(8.0, 7.0, 8.5, 6.5, 100.0)
+                .unwrap()
+                .with_timestamp(2 * MINUTE),
+        ];
+        let outputs: Vec<Vec<Candle>> = bars
+            .into_iter()
+            .map(|bar| resampler.next(heikin_ashi.next_candle(bar)))
+            .collect();
+        assert_eq!(outputs[..2], [vec![], vec![]]);
+        assert_eq!(
+            outputs[2],
+            [Candle::new(11.0, 13.125, 15.0, 9.0, 250.0).unwrap()]
+        );
+        assert_eq!(
+            resampler.flush(),
+            Some(
+                Candle::new(12.0625, 7.5, 12.0625, 6.5, 100.0)
+                    .unwrap()
+                    .with_timestamp(2 * MINUTE)
+            )
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+
+/// the move against a Kagi line needed to start a line in the other direction
+#[derive(Clone, Debug, PartialEq)]
+pub enum KagiReversal {
+    Fixed(f64),
+    /// a percentage of the price the line reached, e.g. `4.0` for 4%
+    Percent(f64),
+}
+
+/// A vertical Kagi line from `start` to `end`. Lines turn yang, or thick, when they rise above
+/// the previous shoulder, and yin, or thin, when they fall below the previous waist. `yang` is
+/// the thickness at the end of the line
+#[derive(Clone, Debug, PartialEq)]
+pub struct KagiLine {
+    pub start: f64,
+    pub end: f64,
+    pub yang: bool,
+}
+
+pub struct KagiFactory {
+    reversal: KagiReversal,
+}
+
+/// Builds Kagi lines from closes. A line extends while the close moves in its direction, and is
+/// completed by a reversal, which starts the next line from its end. The first line starts at
+/// the first close and begins once the close moves the reversal amount away from it
+#[derive(Clone, Debug)]
+pub struct Kagi {
+    reversal: KagiReversal,
+    partial: Option<KagiLine>,
+    shoulder: Option<f64>,
+    waist: Option<f64>,
+}
+
+impl KagiFactory {
+    pub fn new() -> Self {
+        Self {
+            reversal: KagiReversal::Percent(4.0),
+        }
+    }
+
+    pub fn with_reversal(mut self, reversal: KagiReversal) -> Self {
+        self.reversal = reversal;
+        self
+    }
+
+    pub fn build(self) -> Result<Kagi, Error> {
+        let (KagiReversal::Fixed(amount) | KagiReversal::Percent(amount)) = self.reversal;
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(anyhow!("Reversal must be positive. You used {}", amount));
+        }
+        Ok(Kagi {
+            reversal: self.reversal,
+            partial: None,
+            shoulder: None,
+            waist: None,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for Kagi {
+    type Output = Vec<KagiLine>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let close = next.close();
+        let line = self.partial.get_or_insert(KagiLine {
+            start: close,
+            end: close,
+            yang: false,
+        });
+        let reversal = match self.reversal {
+            KagiReversal::Fixed(amount) => amount,
+            KagiReversal::Percent(percent) => line.end.abs() * percent / 100.0,
+        };
+
+        let rising = line.end > line.start;
+        let falling = line.end < line.start;
+        let mut completed = Vec::new();
+        if (rising && close > line.end) || (falling && close < line.end) {
+            line.end = close;
+        } else if line.start == line.end {
+            // the first line begins with a full reversal from the first close
+            if (close - line.start).abs() >= reversal {
+                line.end = close;
+            }
+        } else if (close - line.end).abs() >= reversal {
+            let (end, yang) = (line.end, line.yang);
+            if rising {
+                self.shoulder = Some(end);
+            } else {
+                self.waist = Some(end);
+            }
+            completed.push(
+                self.partial
+                    .replace(KagiLine {
+                        start: end,
+                        end: close,
+                        yang,
+                    })
+                    .unwrap(),
+            );
+        }
+
+        let line = self.partial.as_mut().unwrap();
+        if self.shoulder.is_some_and(|shoulder| line.end > shoulder) {
+            line.yang = true;
+        } else if self.waist.is_some_and(|waist| line.end < waist) {
+            line.yang = false;
+        }
+        completed
+    }
+}
+
+impl Kagi {
+    pub fn factory() -> KagiFactory {
+        KagiFactory::new()
+    }
+
+    /// the line in progress, once there has been an input
+    pub fn partial(&self) -> Option<&KagiLine> {
+        self.partial.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bars::*, *};
+
+    fn line(start: f64, end: f64, yang: bool) -> KagiLine {
+        KagiLine { start, end, yang }
+    }
+
+    #[test]
+    fn test_build() {
+        Kagi::factory()
+            .with_reversal(KagiReversal::Fixed(2.0))
+            .build()
+            .unwrap();
+        assert!(Kagi::factory()
+            .with_reversal(KagiReversal::Percent(0.0))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kagi = Kagi::factory()
+            .with_reversal(KagiReversal::Fixed(2.0))
+            .build()
+            .unwrap();
+
+        assert_eq!(kagi.next(10.0), []);
+        assert_eq!(kagi.next(11.0), []);
+        assert_eq!(kagi.next(12.0), []);
+        assert_eq!(kagi.next(14.0), []);
+        assert_eq!(kagi.next(12.5), []);
+        assert_eq!(kagi.next(11.0), [line(10.0, 14.0, false)]);
+        assert_eq!(kagi.next(13.0), [line(14.0, 11.0, false)]);
+        // rising above the 14.0 shoulder turns the line yang
+        assert_eq!(kagi.next(15.0), []);
+        assert_eq!(kagi.partial(), Some(&line(11.0, 15.0, true)));
+        assert_eq!(kagi.next(12.0), [line(11.0, 15.0, true)]);
+        // and falling below the 11.0 waist turns it yin
+        assert_eq!(kagi.next(10.0), []);
+        assert_eq!(kagi.partial(), Some(&line(15.0, 10.0, false)));
+    }
+}
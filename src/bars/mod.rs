@@ -0,0 +1,29 @@
+//! Builders that turn a stream of trades into `Candle`s, transforms from `Candle`s into longer
+//! or smoothed ones, and price-only charts such as Renko
+
+mod bar_builder;
+pub use bar_builder::{BarBuilder, BarKind, Session};
+
+mod heikin_ashi;
+pub use heikin_ashi::HeikinAshi;
+
+mod kagi;
+pub use kagi::{Kagi, KagiLine, KagiReversal};
+
+mod multi_timeframe;
+pub use multi_timeframe::{MultiTimeframe, MultiTimeframeResult};
+
+mod point_and_figure;
+pub use point_and_figure::{ColumnKind, PointAndFigure, PointAndFigureColumn};
+
+mod range_bars;
+pub use range_bars::RangeBars;
+
+mod renko;
+pub use renko::{Brick, Renko, RenkoBrickSize};
+
+mod resampler;
+pub use resampler::{Resampler, Timeframe};
+
+mod trade;
+pub use trade::Trade;
@@ -0,0 +1,200 @@
+use crate::{bars::Resampler, *};
+use anyhow::Error;
+
+pub struct MultiTimeframeFactory<I> {
+    indicator: I,
+    resampler: Resampler,
+    provisional: bool,
+}
+
+/// The value of the higher timeframe indicator as of the latest lower timeframe input
+#[derive(Clone, PartialEq, Debug)]
+pub struct MultiTimeframeResult<O> {
+    /// the value after the last completed higher timeframe candle, or `None` before the first
+    pub value: Option<O>,
+    /// the value the indicator would have if the candle in progress completed now. `None` unless
+    /// enabled, and whenever no candle is in progress
+    pub provisional: Option<O>,
+}
+
+/// Runs an indicator on a higher timeframe while being fed lower timeframe `Candle`s, which are
+/// aggregated by a `Resampler`. Single series indicators can be wrapped in `Sourced` first. The
+/// provisional value is a `peek`, so the indicator itself only ever sees completed candles
+#[derive(Clone, Debug)]
+pub struct MultiTimeframe<I: Indicator<Candle>> {
+    indicator: I,
+    resampler: Resampler,
+    provisional: bool,
+    value: Option<I::Output>,
+}
+
+impl<I: Indicator<Candle> + Clone> MultiTimeframeFactory<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            resampler: Resampler::factory().build().unwrap(),
+            provisional: false,
+        }
+    }
+
+    /// the resampler building the higher timeframe candles. Defaults to daily candles
+    pub fn with_resampler(mut self, resampler: Resampler) -> Self {
+        self.resampler = resampler;
+        self
+    }
+
+    pub fn with_provisional(mut self, provisional: bool) -> Self {
+        self.provisional = provisional;
+        self
+    }
+
+    pub fn build(self) -> Result<MultiTimeframe<I>, Error> {
+        Ok(MultiTimeframe {
+            indicator: self.indicator,
+            resampler: self.resampler,
+            provisional: self.provisional,
+            value: None,
+        })
+    }
+}
+
+impl<I: Indicator<Candle> + Clone> Indicator<Candle> for MultiTimeframe<I>
+where
+    I::Output: Clone,
+{
+    type Output = MultiTimeframeResult<I::Output>;
+    fn next(&mut self, next: Candle) -> Self::Output {
+        for bar in self.resampler.next(next) {
+            self.value = Some(self.indicator.next(bar));
+        }
+        let provisional = match (self.provisional, self.resampler.partial()) {
+            (true, Some(bar)) => Some(self.indicator.peek(bar.clone())),
+            _ => None,
+        };
+        MultiTimeframeResult {
+            value: self.value.clone(),
+            provisional,
+        }
+    }
+}
+
+impl<I: Indicator<Candle> + Clone> MultiTimeframe<I>
+where
+    I::Output: Clone,
+{
+    pub fn factory(indicator: I) -> MultiTimeframeFactory<I> {
+        MultiTimeframeFactory::new(indicator)
+    }
+
+    /// completes the higher timeframe candle in progress early, for example at the end of the
+    /// data, and returns the indicator's value for it
+    pub fn flush(&mut self) -> Option<I::Output> {
+        let bar = self.resampler.flush()?;
+        self.value = Some(self.indicator.next(bar));
+        self.value.clone()
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn resampler(&self) -> &Resampler {
+        &self.resampler
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bars::{MultiTimeframe, MultiTimeframeResult, Resampler, Timeframe},
+        indicators::{EMA, RSI},
+        *,
+    };
+
+    const MINUTE: i64 = 60_000_000_000;
+
+    #[test]
+    fn test_build() {
+        let rsi = Sourced::new(PriceSource::Close, RSI::<EMA>::factory().build().unwrap());
+        MultiTimeframe::factory(rsi)
+            .with_resampler(
+                Resampler::factory()
+                    .with_timeframe(Timeframe::Week)
+                    .build()
+                    .unwrap(),
+            )
+            .with_provisional(true)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let rsi = Sourced::new(PriceSource::Close, RSI::<EMA>::factory().build().unwrap());
+        let resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(3 * MINUTE))
+            .with_input_interval(MINUTE)
+            .build()
+            .unwrap();
+        let mut multi_timeframe = MultiTimeframe::factory(rsi.clone())
+            .with_resampler(resampler)
+            .with_provisional(true)
+            .build()
+            .unwrap();
+        let mut higher = rsi;
+
+        let closes = [10.0, 11.0, 12.0, 11.5, 11.0, 10.0, 10.5, 12.5];
+        let mut results = Vec::new();
+        for (i, close) in closes.into_iter().enumerate() {
+            let candle = Candle::new(close, close, close, close, 1.0)
+                .unwrap()
+                .with_timestamp(i as i64 * MINUTE);
+            results.push(multi_timeframe.next(candle));
+        }
+
+        // the higher timeframe closes at 12.0 and 10.0, and is at 12.5 when the data ends
+        let provisional = |rsi: &RSI<EMA>, close: f64| rsi.clone().next(close);
+        assert_eq!(
+            results[0],
+            MultiTimeframeResult {
+                value: None,
+                provisional: Some(provisional(higher.inner(), 10.0))
+            }
+        );
+        let first = higher.next(Candle::new(10.0, 12.0, 12.0, 10.0, 3.0).unwrap());
+        assert_eq!(
+            results[2],
+            MultiTimeframeResult {
+                value: Some(first),
+                provisional: None
+            }
+        );
+        assert_eq!(
+            results[3],
+            MultiTimeframeResult {
+                value: Some(first),
+                provisional: Some(provisional(higher.inner(), 11.5))
+            }
+        );
+        let second = higher.next(Candle::new(11.5, 10.0, 11.5, 10.0, 3.0).unwrap());
+        assert_eq!(
+            results[5],
+            MultiTimeframeResult {
+                value: Some(second),
+                provisional: None
+            }
+        );
+        assert_eq!(
+            results[7],
+            MultiTimeframeResult {
+                value: Some(second),
+                provisional: Some(provisional(higher.inner(), 12.5))
+            }
+        );
+        assert_eq!(
+            multi_timeframe.flush(),
+            Some(higher.next(Candle::new(10.5, 12.5, 12.5, 10.5, 2.0).unwrap()))
+        );
+        assert_eq!(multi_timeframe.flush(), None);
+    }
+}
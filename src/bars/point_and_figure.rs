@@ -0,0 +1,239 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColumnKind {
+    /// a rising column
+    X,
+    /// a falling column
+    O,
+}
+
+/// A Point & Figure column, with the price levels of its highest and lowest boxes
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointAndFigureColumn {
+    pub kind: ColumnKind,
+    pub high: f64,
+    pub low: f64,
+    pub boxes: usize,
+}
+
+pub struct PointAndFigureFactory {
+    box_size: f64,
+    reversal: usize,
+}
+
+/// Builds Point & Figure columns from closes. Boxes sit at multiples of the box size. A column of
+/// Xs extends while the close reaches a higher box, and a column of Os while it reaches a lower
+/// one. A move of `reversal` boxes against the column completes it and starts the next column
+/// one box from its end. The first close sets the reference level, and the first column starts
+/// once the close moves a box from it in either direction. Closes within a rounding error of a
+/// box are taken to be on it
+#[derive(Clone, Debug)]
+pub struct PointAndFigure {
+    box_size: f64,
+    reversal: i64,
+    // the first close in boxes
+    reference: Option<f64>,
+    // the kind and the highest and lowest box indices of the column in progress
+    column: Option<(ColumnKind, i64, i64)>,
+}
+
+impl PointAndFigureFactory {
+    pub fn new() -> Self {
+        Self {
+            box_size: 1.0,
+            reversal: 3,
+        }
+    }
+
+    pub fn with_box_size(mut self, box_size: f64) -> Self {
+        self.box_size = box_size;
+        self
+    }
+
+    /// the number of boxes a reversal takes
+    pub fn with_reversal(mut self, reversal: usize) -> Self {
+        self.reversal = reversal;
+        self
+    }
+
+    pub fn build(self) -> Result<PointAndFigure, Error> {
+        if self.box_size <= 0.0 || !self.box_size.is_finite() {
+            return Err(anyhow!(
+                "Box size must be positive. You used {}",
+                self.box_size
+            ));
+        }
+        if self.reversal == 0 {
+            return Err(anyhow!(
+                "Reversal must be at least one box. You used {}",
+                self.reversal
+            ));
+        }
+        Ok(PointAndFigure {
+            box_size: self.box_size,
+            reversal: self.reversal as i64,
+            reference: None,
+            column: None,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for PointAndFigure {
+    type Output = Vec<PointAndFigureColumn>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let mut level = next.close() / self.box_size;
+        // a box size such as 0.1 has no exact representation, so 0.3 would fall just short of
+        // its box
+        if (level - level.round()).abs() < 1e-9 {
+            level = level.round();
+        }
+        // the highest box the close fills when rising, and the lowest when falling
+        let (up, down) = (level.floor() as i64, level.ceil() as i64);
+
+        let column = match self.column.take() {
+            Some(column) => column,
+            None => {
+                let reference = *self.reference.get_or_insert(level);
+                if level >= reference + 1.0 {
+                    self.column = Some((ColumnKind::X, up, reference.floor() as i64 + 1));
+                } else if level <= reference - 1.0 {
+                    self.column = Some((ColumnKind::O, reference.ceil() as i64 - 1, down));
+                }
+                return Vec::new();
+            }
+        };
+
+        let mut completed = Vec::new();
+        self.column = Some(match column {
+            (ColumnKind::X, high, low) if up > high => (ColumnKind::X, up, low),
+            (ColumnKind::X, high, low) if down <= high - self.reversal => {
+                completed.push(self.column(ColumnKind::X, high, low));
+                (ColumnKind::O, high - 1, down)
+            }
+            (ColumnKind::O, high, low) if down < low => (ColumnKind::O, high, down),
+            (ColumnKind::O, high, low) if up >= low + self.reversal => {
+                completed.push(self.column(ColumnKind::O, high, low));
+                (ColumnKind::X, up, low + 1)
+            }
+            column => column,
+        });
+        completed
+    }
+}
+
+impl PointAndFigure {
+    pub fn factory() -> PointAndFigureFactory {
+        PointAndFigureFactory::new()
+    }
+
+    /// the column in progress, once the close has moved a box from the first close
+    pub fn partial(&self) -> Option<PointAndFigureColumn> {
+        let (kind, high, low) = self.column.clone()?;
+        Some(self.column(kind, high, low))
+    }
+
+    fn column(&self, kind: ColumnKind, high: i64, low: i64) -> PointAndFigureColumn {
+        PointAndFigureColumn {
+            kind,
+            high: high as f64 * self.box_size,
+            low: low as f64 * self.box_size,
+            boxes: (high - low + 1) as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bars::*, *};
+
+    fn column(kind: ColumnKind, high: f64, low: f64, boxes: usize) -> PointAndFigureColumn {
+        PointAndFigureColumn {
+            kind,
+            high,
+            low,
+            boxes,
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        PointAndFigure::factory()
+            .with_box_size(0.5)
+            .with_reversal(1)
+            .build()
+            .unwrap();
+        assert!(PointAndFigure::factory()
+            .with_box_size(0.0)
+            .build()
+            .is_err());
+        assert!(PointAndFigure::factory().with_reversal(0).build().is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut point_and_figure = PointAndFigure::factory().build().unwrap();
+
+        assert_eq!(point_and_figure.next(10.2), []);
+        assert_eq!(point_and_figure.partial(), None);
+        assert_eq!(point_and_figure.next(11.5), []);
+        assert_eq!(point_and_figure.next(13.7), []);
+        assert_eq!(
+            point_and_figure.partial(),
+            Some(column(ColumnKind::X, 13.0, 11.0, 3))
+        );
+        // two boxes down is not a reversal
+        assert_eq!(point_and_figure.next(11.0), []);
+        assert_eq!(
+            point_and_figure.next(9.8),
+            [column(ColumnKind::X, 13.0, 11.0, 3)]
+        );
+        assert_eq!(
+            point_and_figure.partial(),
+            Some(column(ColumnKind::O, 12.0, 10.0, 3))
+        );
+        assert_eq!(point_and_figure.next(8.0), []);
+        assert_eq!(
+            point_and_figure.next(11.0),
+            [column(ColumnKind::O, 12.0, 8.0, 5)]
+        );
+        assert_eq!(
+            point_and_figure.partial(),
+            Some(column(ColumnKind::X, 11.0, 9.0, 3))
+        );
+    }
+
+    #[test]
+    fn test_fractional_box() {
+        let mut point_and_figure = PointAndFigure::factory()
+            .with_box_size(0.1)
+            .build()
+            .unwrap();
+
+        assert_eq!(point_and_figure.next(0.1), []);
+        // 0.3 / 0.1 is just below 3.0 but still fills the box at 0.3
+        assert_eq!(point_and_figure.next(0.3), []);
+        assert_eq!(
+            point_and_figure.partial(),
+            Some(column(ColumnKind::X, 3.0 * 0.1, 2.0 * 0.1, 2))
+        );
+    }
+
+    #[test]
+    fn test_first_column() {
+        let mut point_and_figure = PointAndFigure::factory().build().unwrap();
+
+        // less than a box either way from the first close starts no column
+        assert_eq!(point_and_figure.next(10.9), []);
+        assert_eq!(point_and_figure.next(11.8), []);
+        assert_eq!(point_and_figure.next(10.0), []);
+        assert_eq!(point_and_figure.partial(), None);
+        // a box down starts a column of Os, as a box up would one of Xs
+        assert_eq!(point_and_figure.next(9.8), []);
+        assert_eq!(
+            point_and_figure.partial(),
+            Some(column(ColumnKind::O, 10.0, 10.0, 1))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+
+pub struct RangeBarsFactory {
+    range: f64,
+}
+
+/// Builds bars from closes that each span exactly `range` from high to low. A bar completes when
+/// the close moves outside its range, at the edge it crossed, and the next bar opens there. A
+/// jump of several ranges completes several bars. Volume and timestamp are zero
+#[derive(Clone, Debug)]
+pub struct RangeBars {
+    range: f64,
+    partial: Option<Candle>,
+}
+
+impl RangeBarsFactory {
+    pub fn new() -> Self {
+        Self { range: 1.0 }
+    }
+
+    pub fn with_range(mut self, range: f64) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn build(self) -> Result<RangeBars, Error> {
+        if self.range <= 0.0 || !self.range.is_finite() {
+            return Err(anyhow!("Range must be positive. You used {}", self.range));
+        }
+        Ok(RangeBars {
+            range: self.range,
+            partial: None,
+        })
+    }
+}
+
+fn flat(price: f64) -> Candle {
+    Candle {
+        timestamp: 0,
+        open: price,
+        close: price,
+        high: price,
+        low: price,
+        volume: 0.0,
+        trade_count: None,
+        vwap: None,
+    }
+}
+
+impl<T: Close> Indicator<T> for RangeBars {
+    type Output = Vec<Candle>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let close = next.close();
+        let bar = self.partial.get_or_insert_with(|| flat(close));
+        let mut bars = Vec::new();
+        loop {
+            let edge = if close > bar.low + self.range {
+                bar.low + self.range
+            } else if close < bar.high - self.range {
+                bar.high - self.range
+            } else {
+                break;
+            };
+            bar.high = bar.high.max(edge);
+            bar.low = bar.low.min(edge);
+            bar.close = edge;
+            bars.push(std::mem::replace(bar, flat(edge)));
+        }
+        bar.high = bar.high.max(close);
+        bar.low = bar.low.min(close);
+        bar.close = close;
+        bars
+    }
+}
+
+impl RangeBars {
+    pub fn factory() -> RangeBarsFactory {
+        RangeBarsFactory::new()
+    }
+
+    /// the bar in progress, once there has been an input
+    pub fn partial(&self) -> Option<&Candle> {
+        self.partial.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bars::RangeBars, *};
+
+    fn bar(open: f64, close: f64, high: f64, low: f64) -> Candle {
+        Candle::new(open, close, high, low, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        RangeBars::factory().with_range(0.5).build().unwrap();
+        assert!(RangeBars::factory().with_range(-1.0).build().is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut range_bars = RangeBars::factory().with_range(2.0).build().unwrap();
+
+        assert_eq!(range_bars.next(10.0), []);
+        assert_eq!(range_bars.next(9.0), []);
+        assert_eq!(range_bars.next(11.5), [bar(10.0, 11.0, 11.0, 9.0)]);
+        assert_eq!(range_bars.partial(), Some(&bar(11.0, 11.5, 11.5, 11.0)));
+        assert_eq!(
+            range_bars.next(6.0),
+            [bar(11.0, 9.5, 11.5, 9.5), bar(9.5, 7.5, 9.5, 7.5)]
+        );
+        assert_eq!(range_bars.partial(), Some(&bar(7.5, 6.0, 7.5, 6.0)));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{indicators::ATR, *};
+use anyhow::{anyhow, Error};
+
+/// the price move each Renko brick represents
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenkoBrickSize {
+    Fixed(f64),
+    /// `multiplier` times the `ATR` over `window_size` bars, as of the bar forming the brick. No
+    /// bricks form until the `ATR` has seen `window_size` bars, but the first close is still the
+    /// base
+    Atr {
+        window_size: usize,
+        multiplier: f64,
+    },
+}
+
+/// A Renko brick, rising when `close` is above `open`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Brick {
+    pub open: f64,
+    pub close: f64,
+}
+
+impl Brick {
+    pub fn is_up(&self) -> bool {
+        self.close > self.open
+    }
+}
+
+// the brick size, with the `ATR` that sizes the bricks when there is one
+#[derive(Clone, Debug)]
+enum Sizing {
+    Fixed(f64),
+    Atr {
+        atr: ATR,
+        window_size: usize,
+        multiplier: f64,
+    },
+}
+
+pub struct RenkoFactory {
+    brick_size: RenkoBrickSize,
+}
+
+/// Builds Renko bricks from closes. A brick forms each time the close moves a brick size beyond
+/// the last brick in its direction, or a brick size beyond its open against it, so reversals
+/// need twice the move. The first close is the base of the first brick. Candles also feed the
+/// `ATR` for ATR sized bricks, while plain closes feed it their changes
+#[derive(Clone, Debug)]
+pub struct Renko {
+    sizing: Sizing,
+    count: usize,
+    base: Option<f64>,
+    last: Option<Brick>,
+}
+
+impl RenkoFactory {
+    pub fn new() -> Self {
+        Self {
+            brick_size: RenkoBrickSize::Fixed(1.0),
+        }
+    }
+
+    pub fn with_brick_size(mut self, brick_size: RenkoBrickSize) -> Self {
+        self.brick_size = brick_size;
+        self
+    }
+
+    pub fn build(self) -> Result<Renko, Error> {
+        let sizing = match self.brick_size {
+            RenkoBrickSize::Fixed(size) => {
+                if size <= 0.0 || !size.is_finite() {
+                    return Err(anyhow!("Brick size must be positive. You used {}", size));
+                }
+                Sizing::Fixed(size)
+            }
+            RenkoBrickSize::Atr {
+                window_size,
+                multiplier,
+            } => {
+                if multiplier <= 0.0 || !multiplier.is_finite() {
+                    return Err(anyhow!(
+                        "ATR multiplier must be positive. You used {}",
+                        multiplier
+                    ));
+                }
+                Sizing::Atr {
+                    atr: ATR::factory().with_window_size(window_size).build()?,
+                    window_size,
+                    multiplier,
+                }
+            }
+        };
+        Ok(Renko {
+            sizing,
+            count: 0,
+            base: None,
+            last: None,
+        })
+    }
+}
+
+impl<T: Close + High + Low> Indicator<T> for Renko {
+    type Output = Vec<Brick>;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.push(next.close(), next.high(), next.low())
+    }
+}
+
+impl Indicator<f64> for Renko {
+    type Output = Vec<Brick>;
+    fn next(&mut self, next: f64) -> Self::Output {
+        self.push(next, next, next)
+    }
+}
+
+impl Renko {
+    pub fn factory() -> RenkoFactory {
+        RenkoFactory::new()
+    }
+
+    fn push(&mut self, close: f64, high: f64, low: f64) -> Vec<Brick> {
+        self.count += 1;
+        let base = *self.base.get_or_insert(close);
+        let size = match &mut self.sizing {
+            Sizing::Fixed(size) => *size,
+            Sizing::Atr {
+                atr,
+                window_size,
+                multiplier,
+            } => {
+                let candle = Candle {
+                    timestamp: 0,
+                    open: close,
+                    close,
+                    high,
+                    low,
+                    volume: 0.0,
+                    trade_count: None,
+                    vwap: None,
+                };
+                let atr = atr.next(candle);
+                if self.count < *window_size {
+                    return Vec::new();
+                }
+                atr * *multiplier
+            }
+        };
+        if size <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut bricks = Vec::new();
+        loop {
+            let (top, bottom) = match &self.last {
+                Some(brick) => (brick.open.max(brick.close), brick.open.min(brick.close)),
+                None => (base, base),
+            };
+            let brick = if close >= top + size {
+                Brick {
+                    open: top,
+                    close: top + size,
+                }
+            } else if close <= bottom - size {
+                Brick {
+                    open: bottom,
+                    close: bottom - size,
+                }
+            } else {
+                break;
+            };
+            self.last = Some(brick.clone());
+            bricks.push(brick);
+        }
+        bricks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bars::*, *};
+
+    fn brick(open: f64, close: f64) -> Brick {
+        Brick { open, close }
+    }
+
+    #[test]
+    fn test_build() {
+        Renko::factory()
+            .with_brick_size(RenkoBrickSize::Atr {
+                window_size: 14,
+                multiplier: 0.5,
+            })
+            .build()
+            .unwrap();
+        assert!(Renko::factory()
+            .with_brick_size(RenkoBrickSize::Fixed(0.0))
+            .build()
+            .is_err());
+        assert!(Renko::factory()
+            .with_brick_size(RenkoBrickSize::Atr {
+                window_size: 1,
+                multiplier: 1.0
+            })
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_fixed() {
+        let mut renko = Renko::factory()
+            .with_brick_size(RenkoBrickSize::Fixed(2.0))
+            .build()
+            .unwrap();
+
+        assert_eq!(renko.next(10.0), []);
+        assert_eq!(renko.next(11.9), []);
+        assert_eq!(
+            renko.next(16.5),
+            [brick(10.0, 12.0), brick(12.0, 14.0), brick(14.0, 16.0)]
+        );
+        // a reversal needs a brick size below the last brick's open
+        assert_eq!(renko.next(12.5), []);
+        assert_eq!(renko.next(12.0), [brick(14.0, 12.0)]);
+        assert_eq!(renko.next(15.0), []);
+        assert_eq!(renko.next(16.0), [brick(14.0, 16.0)]);
+        assert!(brick(14.0, 16.0).is_up());
+    }
+
+    #[test]
+    fn test_atr() {
+        let mut renko = Renko::factory()
+            .with_brick_size(RenkoBrickSize::Atr {
+                window_size: 2,
+                multiplier: 1.0,
+            })
+            .build()
+            .unwrap();
+
+        // the ATR only sizes bricks once it has seen two bars
+        assert_eq!(
+            renko.next(Candle::new(10.0, 10.0, 11.0, 9.0, 0.0).unwrap()),
+            []
+        );
+        assert_eq!(
+            renko.next(Candle::new(10.0, 10.0, 11.0, 9.0, 0.0).unwrap()),
+            []
+        );
+        // true ranges of 2.0 and 5.0 make a 3.5 brick
+        assert_eq!(
+            renko.next(Candle::new(12.0, 14.0, 15.0, 12.0, 0.0).unwrap()),
+            [brick(10.0, 13.5)]
+        );
+    }
+
+    #[test]
+    fn test_atr_base() {
+        let mut renko = Renko::factory()
+            .with_brick_size(RenkoBrickSize::Atr {
+                window_size: 2,
+                multiplier: 1.0,
+            })
+            .build()
+            .unwrap();
+
+        // the first close is the base even though the ATR is still warming up
+        assert_eq!(
+            renko.next(Candle::new(10.0, 10.0, 11.0, 9.0, 0.0).unwrap()),
+            []
+        );
+        assert_eq!(
+            renko.next(Candle::new(10.0, 12.0, 13.0, 10.0, 0.0).unwrap()),
+            []
+        );
+        // the ATR falls to 1.75, a brick above 10.0 but not above 12.0
+        assert_eq!(
+            renko.next(Candle::new(12.0, 13.0, 13.0, 12.0, 0.0).unwrap()),
+            [brick(10.0, 11.75)]
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+
+const DAY: i64 = 86_400_000_000_000;
+
+/// the period of the candles a `Resampler` builds
+#[derive(Clone, Debug, PartialEq)]
+pub enum Timeframe {
+    /// a fixed number of nanoseconds, aligned to the Unix epoch. One day is `86_400_000_000_000`
+    Duration(i64),
+    /// calendar weeks starting on Monday
+    Week,
+    /// this many calendar months, aligned to the start of the year when they divide twelve. Use
+    /// 3 for quarters and 12 for years
+    Month(u32),
+}
+
+pub struct ResamplerFactory {
+    timeframe: Timeframe,
+    utc_offset: i64,
+    input_interval: Option<i64>,
+    fill_gaps: bool,
+}
+
+/// Aggregates `Candle`s into candles of a longer `Timeframe`: the first open, the highest high,
+/// the lowest low, the last close and the summed volume. Trade counts are summed and VWAPs
+/// volume weighted when every input has them. Output candles are timestamped with the start of
+/// their period, and inputs are assigned to periods by their own timestamp, so inputs should be
+/// timestamped with their open.
+///
+/// `next` returns the candles the input completes, oldest first. Without an input interval, a
+/// candle completes when an input from a later period arrives. With one, it also completes as
+/// soon as an input ending at the period end arrives, so a higher timeframe indicator can be fed
+/// on the same step as a lower timeframe one. Inputs older than the partial candle are added to
+/// it
+#[derive(Clone, Debug)]
+pub struct Resampler {
+    timeframe: Timeframe,
+    utc_offset: i64,
+    input_interval: Option<i64>,
+    fill_gaps: bool,
+    partial: Option<Candle>,
+    // the end of the partial candle's period
+    end: i64,
+    // the end and close of the last completed candle, to fill gaps from
+    last: Option<(i64, f64)>,
+    weighted_vwap: Option<f64>,
+}
+
+impl ResamplerFactory {
+    pub fn new() -> Self {
+        Self {
+            timeframe: Timeframe::Duration(DAY),
+            utc_offset: 0,
+            input_interval: None,
+            fill_gaps: false,
+        }
+    }
+
+    pub fn with_timeframe(mut self, timeframe: Timeframe) -> Self {
+        self.timeframe = timeframe;
+        self
+    }
+
+    /// the offset of local time from UTC in nanoseconds, so days, weeks and months start at local
+    /// midnight. Also moves the boundaries of fixed durations
+    pub fn with_utc_offset(mut self, utc_offset: i64) -> Self {
+        self.utc_offset = utc_offset;
+        self
+    }
+
+    /// the duration of the input candles in nanoseconds
+    pub fn with_input_interval(mut self, input_interval: i64) -> Self {
+        self.input_interval = Some(input_interval);
+        self
+    }
+
+    /// whether periods without inputs produce flat candles at the last close with zero volume,
+    /// instead of nothing
+    pub fn with_fill_gaps(mut self, fill_gaps: bool) -> Self {
+        self.fill_gaps = fill_gaps;
+        self
+    }
+
+    pub fn build(self) -> Result<Resampler, Error> {
+        match self.timeframe {
+            Timeframe::Duration(duration) if duration <= 0 => {
+                return Err(anyhow!(
+                    "Timeframe duration must be positive. You used {}",
+                    duration
+                ));
+            }
+            Timeframe::Month(0) => {
+                return Err(anyhow!("Timeframe must be at least one month. You used 0"))
+            }
+            _ => {}
+        }
+        if let Some(input_interval) = self.input_interval.filter(|interval| *interval <= 0) {
+            return Err(anyhow!(
+                "Input interval must be positive. You used {}",
+                input_interval
+            ));
+        }
+        Ok(Resampler {
+            timeframe: self.timeframe,
+            utc_offset: self.utc_offset,
+            input_interval: self.input_interval,
+            fill_gaps: self.fill_gaps,
+            partial: None,
+            end: 0,
+            last: None,
+            weighted_vwap: None,
+        })
+    }
+}
+
+impl Indicator<Candle> for Resampler {
+    type Output = Vec<Candle>;
+    fn next(&mut self, next: Candle) -> Self::Output {
+        let mut completed: Vec<Candle> = self.advance_to(next.timestamp).into_iter().collect();
+        // gaps can only open before a new partial candle
+        if self.fill_gaps && self.partial.is_none() {
+            if let Some((mut end, close)) = self.last {
+                while end <= next.timestamp {
+                    let (start, next_end) = self.period(end);
+                    if next_end > next.timestamp {
+                        break;
+                    }
+                    completed.push(
+                        Candle::new(close, close, close, close, 0.0)
+                            .unwrap()
+                            .with_timestamp(start),
+                    );
+                    end = next_end;
+                }
+            }
+        }
+
+        match &mut self.partial {
+            Some(bar) => {
+                bar.high = bar.high.max(next.high);
+                bar.low = bar.low.min(next.low);
+                bar.close = next.close;
+                bar.volume += next.volume;
+                bar.trade_count = bar.trade_count.zip(next.trade_count).map(|(a, b)| a + b);
+                self.weighted_vwap = self
+                    .weighted_vwap
+                    .zip(next.vwap)
+                    .map(|(sum, vwap)| sum + vwap * next.volume);
+            }
+            None => {
+                let (start, end) = self.period(next.timestamp);
+                self.end = end;
+                self.weighted_vwap = next.vwap.map(|vwap| vwap * next.volume);
+                self.partial = Some(Candle {
+                    timestamp: start,
+                    vwap: None,
+                    ..next.clone()
+                });
+            }
+        }
+
+        if self
+            .input_interval
+            .is_some_and(|interval| next.timestamp + interval >= self.end)
+        {
+            completed.extend(self.flush());
+        }
+        completed
+    }
+}
+
+impl Resampler {
+    pub fn factory() -> ResamplerFactory {
+        ResamplerFactory::new()
+    }
+
+    /// the candle in progress, if it has any inputs. Its VWAP is filled in when it completes
+    pub fn partial(&self) -> Option<&Candle> {
+        self.partial.as_ref()
+    }
+
+    /// completes the partial candle early, for example at the end of the data
+    pub fn flush(&mut self) -> Option<Candle> {
+        let mut bar = self.partial.take()?;
+        if bar.volume > 0.0 {
+            bar.vwap = self
+                .weighted_vwap
+                .map(|sum| (sum / bar.volume).clamp(bar.low, bar.high));
+        }
+        self.last = Some((self.end, bar.close));
+        Some(bar)
+    }
+
+    /// completes the partial candle if its period ends by `timestamp`
+    pub fn advance_to(&mut self, timestamp: i64) -> Option<Candle> {
+        if self.partial.is_some() && timestamp >= self.end {
+            self.flush()
+        } else {
+            None
+        }
+    }
+
+    /// the start and end of the period containing `timestamp`
+    fn period(&self, timestamp: i64) -> (i64, i64) {
+        let local = timestamp + self.utc_offset;
+        let (start, end) = match self.timeframe {
+            Timeframe::Duration(duration) => {
+                let start = local - local.rem_euclid(duration);
+                (start, start + duration)
+            }
+            Timeframe::Week => {
+                // 1970-01-01 was a Thursday
+                let days = local.div_euclid(DAY);
+                let start = (days - (days + 3).rem_euclid(7)) * DAY;
+                (start, start + 7 * DAY)
+            }
+            Timeframe::Month(months) => {
+                let months = months as i64;
+                let (year, month, _) = civil_from_days(local.div_euclid(DAY));
+                let index = (year - 1970) * 12 + month - 1;
+                let start = index - index.rem_euclid(months);
+                let day = |index: i64| {
+                    days_from_civil(1970 + index.div_euclid(12), index.rem_euclid(12) + 1, 1) * DAY
+                };
+                (day(start), day(start + months))
+            }
+        };
+        (start - self.utc_offset, end - self.utc_offset)
+    }
+}
+
+// Howard Hinnant's conversions between days since the Unix epoch and proleptic Gregorian dates
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, DAY};
+    use crate::{bars::*, indicators::SMA, *};
+
+    const MINUTE: i64 = 60_000_000_000;
+
+    fn candle(timestamp: i64, [open, close, high, low]: [f64; 4], volume: f64) -> Candle {
+        Candle::new(open, close, high, low, volume)
+            .unwrap()
+            .with_timestamp(timestamp)
+    }
+
+    #[test]
+    fn test_build() {
+        Resampler::factory()
+            .with_timeframe(Timeframe::Month(3))
+            .with_utc_offset(-5 * 60 * MINUTE)
+            .with_input_interval(DAY)
+            .with_fill_gaps(true)
+            .build()
+            .unwrap();
+        assert!(Resampler::factory()
+            .with_timeframe(Timeframe::Duration(0))
+            .build()
+            .is_err());
+        assert!(Resampler::factory()
+            .with_timeframe(Timeframe::Month(0))
+            .build()
+            .is_err());
+        assert!(Resampler::factory()
+            .with_input_interval(-1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for days in -800_000..800_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let mut resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(5 * MINUTE))
+            .build()
+            .unwrap();
+
+        let bars = [
+            candle(0, [10.0, 11.0, 11.5, 9.5], 100.0)
+                .with_trade_count(3)
+                .with_vwap(10.5)
+                .unwrap(),
+            candle(MINUTE, [11.0, 12.0, 12.5, 10.5], 300.0)
+                .with_trade_count(5)
+                .with_vwap(12.0)
+                .unwrap(),
+            candle(4 * MINUTE, [12.0, 11.0, 12.0, 10.0], 100.0)
+                .with_trade_count(2)
+                .with_vwap(11.0)
+                .unwrap(),
+            candle(5 * MINUTE, [11.0, 10.0, 11.0, 9.0], 50.0),
+        ];
+        let outputs: Vec<Vec<Candle>> = bars.into_iter().map(|bar| resampler.next(bar)).collect();
+        assert_eq!(outputs[..3], [vec![], vec![], vec![]]);
+        assert_eq!(
+            outputs[3],
+            [candle(0, [10.0, 11.0, 12.5, 9.5], 500.0)
+                .with_trade_count(10)
+                .with_vwap(11.5)
+                .unwrap()]
+        );
+
+        // the second candle has no trade count or VWAP
+        assert_eq!(resampler.advance_to(10 * MINUTE - 1), None);
+        let mut expected = candle(5 * MINUTE, [11.0, 10.0, 11.0, 9.0], 50.0);
+        expected.trade_count = None;
+        assert_eq!(resampler.flush(), Some(expected));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let mut resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(5 * MINUTE))
+            .with_input_interval(MINUTE)
+            .with_fill_gaps(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            resampler.next(candle(3 * MINUTE, [10.0, 11.0, 11.0, 10.0], 1.0)),
+            []
+        );
+        // the last minute of the period completes it straight away
+        assert_eq!(
+            resampler.next(candle(4 * MINUTE, [11.0, 12.0, 12.0, 11.0], 1.0)),
+            [candle(0, [10.0, 12.0, 12.0, 10.0], 2.0)]
+        );
+        assert_eq!(
+            resampler.next(candle(17 * MINUTE, [13.0, 13.0, 13.0, 13.0], 1.0)),
+            [
+                candle(5 * MINUTE, [12.0; 4], 0.0),
+                candle(10 * MINUTE, [12.0; 4], 0.0)
+            ]
+        );
+        assert_eq!(resampler.partial().unwrap().timestamp, 15 * MINUTE);
+    }
+
+    #[test]
+    fn test_gaps_once() {
+        let mut resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(5 * MINUTE))
+            .with_fill_gaps(true)
+            .build()
+            .unwrap();
+
+        let outputs: Vec<Vec<Candle>> = [0, 1, 17, 18, 19]
+            .into_iter()
+            .map(|minute| resampler.next(candle(minute * MINUTE, [10.0; 4], 1.0)))
+            .collect();
+        assert_eq!(
+            outputs[2],
+            [
+                candle(0, [10.0; 4], 2.0),
+                candle(5 * MINUTE, [10.0; 4], 0.0),
+                candle(10 * MINUTE, [10.0; 4], 0.0)
+            ]
+        );
+        // later inputs in the same period don't fill the gap again
+        assert_eq!(outputs[3..], [vec![], vec![]]);
+        assert_eq!(resampler.flush(), Some(candle(15 * MINUTE, [10.0; 4], 3.0)));
+    }
+
+    #[test]
+    fn test_calendar() {
+        // Wednesday 2024-01-31 23:00 and Thursday 2024-02-01 01:00 UTC
+        let january = days_from_civil(2024, 1, 31) * DAY + 23 * 60 * MINUTE;
+        let february = days_from_civil(2024, 2, 1) * DAY + 60 * MINUTE;
+
+        let mut month = Resampler::factory()
+            .with_timeframe(Timeframe::Month(1))
+            .build()
+            .unwrap();
+        let mut week = Resampler::factory()
+            .with_timeframe(Timeframe::Week)
+            .build()
+            .unwrap();
+        let mut quarter = Resampler::factory()
+            .with_timeframe(Timeframe::Month(3))
+            .build()
+            .unwrap();
+        // in UTC+2 both candles fall on February 1
+        let mut local_day = Resampler::factory()
+            .with_utc_offset(2 * 60 * MINUTE)
+            .build()
+            .unwrap();
+
+        for resampler in [&mut month, &mut week, &mut quarter, &mut local_day] {
+            resampler.next(candle(january, [10.0; 4], 1.0));
+        }
+        assert_eq!(month.next(candle(february, [11.0; 4], 1.0)).len(), 1);
+        assert_eq!(
+            month.partial().unwrap().timestamp,
+            days_from_civil(2024, 2, 1) * DAY
+        );
+        assert_eq!(week.next(candle(february, [11.0; 4], 1.0)), []);
+        assert_eq!(
+            week.partial().unwrap().timestamp,
+            days_from_civil(2024, 1, 29) * DAY
+        );
+        assert_eq!(quarter.next(candle(february, [11.0; 4], 1.0)), []);
+        assert_eq!(
+            quarter.partial().unwrap().timestamp,
+            days_from_civil(2024, 1, 1) * DAY
+        );
+        assert_eq!(local_day.next(candle(february, [11.0; 4], 1.0)), []);
+        assert_eq!(
+            local_day.partial().unwrap().timestamp,
+            days_from_civil(2024, 1, 31) * DAY + 22 * 60 * MINUTE
+        );
+    }
+
+    #[test]
+    fn test_higher_timeframe() {
+        let mut resampler = Resampler::factory()
+            .with_timeframe(Timeframe::Duration(5 * MINUTE))
+            .with_input_interval(MINUTE)
+            .build()
+            .unwrap();
+        let mut fast = SMA::factory().with_window_size(5).build().unwrap();
+        let mut slow = SMA::factory().with_window_size(2).build().unwrap();
+
+        let mut slow_values = Vec::new();
+        for i in 0..10 {
+            let close = 10.0 + i as f64;
+            let bar = candle(i * MINUTE, [close; 4], 1.0);
+            fast.next(bar.close);
+            for completed in resampler.next(bar) {
+                slow_values.push(slow.next(completed.close));
+            }
+        }
+        assert_eq!(fast.next(20.0), 18.0);
+        assert_eq!(slow_values, [14.0, 16.5]);
+    }
+}
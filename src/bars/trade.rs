@@ -0,0 +1,38 @@
+use crate::indicator::*;
+
+/// A single trade, the default input of a `BarBuilder`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trade {
+    /// nanoseconds since the Unix epoch
+    pub timestamp: i64,
+    pub price: f64,
+    pub size: f64,
+}
+
+impl Trade {
+    pub fn new(timestamp: i64, price: f64, size: f64) -> Self {
+        Self {
+            timestamp,
+            price,
+            size,
+        }
+    }
+}
+
+impl Timestamp for Trade {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+impl Close for Trade {
+    fn close(&self) -> f64 {
+        self.price
+    }
+}
+
+impl Volume for Trade {
+    fn volume(&self) -> f64 {
+        self.size
+    }
+}
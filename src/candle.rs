@@ -2,38 +2,77 @@ use anyhow::{anyhow, Error};
 
 use crate::indicator::*;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Candle {
+    /// nanoseconds since the Unix epoch at the open of the bar
+    pub timestamp: i64,
     pub open: f64,
     pub close: f64,
     pub high: f64,
     pub low: f64,
-    pub volume: u64,
+    pub volume: f64,
+    /// the number of trades in the bar, if the data source reports it
+    pub trade_count: Option<u64>,
+    /// the volume weighted average price of the bar, if the data source reports it
+    pub vwap: Option<f64>,
 }
 
 /// `Candle`s are the default data item. They may be used as an input to any indicator, as they
-/// implement `Open`, `Close`, `High`, `Low`, and `Volume`
+/// implement `Timestamp`, `Open`, `Close`, `High`, `Low`, and `Volume`
 impl Candle {
-    pub fn new(open: f64, close: f64, high: f64, low: f64, volume: u64) -> Result<Candle, Error> {
-        if [open, close, low].into_iter().all(|x| x <= high) && [open, close].into_iter().all(|x| x >= low) {
+    pub fn new(open: f64, close: f64, high: f64, low: f64, volume: f64) -> Result<Candle, Error> {
+        if [open, close, low].into_iter().all(|x| x <= high) && [open, close].into_iter().all(|x| x >= low) && volume >= 0.0 {
             Ok(Candle {
+                timestamp: 0,
                 open,
                 close,
                 high,
                 low,
                 volume,
+                trade_count: None,
+                vwap: None,
             })
         } else {
             Err(anyhow!(
                 "Unclean candle! Open: {}, Close: {}, High: {}, Low: {}, Volume: {}",
                 open,
                 close,
-                low,
                 high,
+                low,
                 volume,
             ))
         }
     }
+
+    pub fn with_timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_trade_count(mut self, trade_count: u64) -> Self {
+        self.trade_count = Some(trade_count);
+        self
+    }
+
+    /// errors if the VWAP lies outside the range of the bar
+    pub fn with_vwap(mut self, vwap: f64) -> Result<Self, Error> {
+        if !(self.low..=self.high).contains(&vwap) {
+            return Err(anyhow!(
+                "Unclean candle! VWAP: {} is outside High: {}, Low: {}",
+                vwap,
+                self.high,
+                self.low,
+            ));
+        }
+        self.vwap = Some(vwap);
+        Ok(self)
+    }
+}
+
+impl Timestamp for Candle {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
 }
 
 impl Open for Candle {
@@ -61,7 +100,30 @@ impl Low for Candle {
 }
 
 impl Volume for Candle {
-    fn volume(&self) -> u64 {
+    fn volume(&self) -> f64 {
         self.volume
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_new() {
+        let candle = Candle::new(10.0, 12.0, 13.0, 9.0, 1.5)
+            .unwrap()
+            .with_timestamp(1_700_000_000_000_000_000)
+            .with_trade_count(42)
+            .with_vwap(11.25)
+            .unwrap();
+        assert_eq!(candle.timestamp(), 1_700_000_000_000_000_000);
+        assert_eq!(candle.volume(), 1.5);
+        assert_eq!(candle.trade_count, Some(42));
+        assert_eq!(candle.vwap, Some(11.25));
+
+        assert!(Candle::new(10.0, 12.0, 11.0, 9.0, 1.0).is_err());
+        assert!(Candle::new(10.0, 12.0, 13.0, 9.0, -1.0).is_err());
+        assert!(Candle::new(10.0, 12.0, 13.0, 9.0, 1.0).unwrap().with_vwap(14.0).is_err());
+    }
+}
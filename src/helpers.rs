@@ -6,3 +6,25 @@ pub(crate) fn check_window_size(size: usize) -> Result<(), Error> {
     }
     Ok(())
 }
+
+pub(crate) fn check_periods_per_year(periods: f64) -> Result<(), Error> {
+    if periods <= 0.0 || !periods.is_finite() {
+        return Err(anyhow!("Periods per year must be greater than zero and a real number. You used {}", periods));
+    }
+    Ok(())
+}
+
+/// The input for the tests of John Ehlers' indicators. Their reference values come from the
+/// EasyLanguage listings cited in each test, transcribed line for line and run on `price`, so
+/// they check the transcription rather than the listings themselves. Where a filter has a known
+/// response, such as unit gain on a flat price, the tests check that too
+#[cfg(test)]
+pub(crate) mod ehlers {
+    use std::f64::consts::PI;
+
+    /// cycles of 20 and 37 bars on a slow uptrend
+    pub(crate) fn price(i: usize) -> f64 {
+        let i = i as f64;
+        100.0 + 10.0 * (2.0 * PI * i / 20.0).sin() + 5.0 * (2.0 * PI * i / 37.0).sin() + 0.05 * i
+    }
+}
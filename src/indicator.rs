@@ -5,6 +5,26 @@ pub trait Indicator<T> {
     fn next(&mut self, next: T) -> Self::Output;
 }
 
+/// trait `Peek<T>` evaluates an `Indicator` on a candidate input without committing it, for
+/// example to show the value of a bar that is still forming. It is implemented for every
+/// `Indicator` that is `Clone`, by advancing a clone, so each call costs a copy of the state. To
+/// revise the latest input instead, wrap the indicator in an `Intrabar`
+pub trait Peek<T>: Indicator<T> {
+    /// the output `next(next)` would return, leaving the indicator unchanged
+    fn peek(&self, next: T) -> Self::Output;
+}
+
+impl<T, I: Indicator<T> + Clone> Peek<T> for I {
+    fn peek(&self, next: T) -> Self::Output {
+        self.clone().next(next)
+    }
+}
+
+/// trait `Timestamp` indicates the time of a data item, in nanoseconds since the Unix epoch
+pub trait Timestamp {
+    fn timestamp(&self) -> i64;
+}
+
 /// trait `Open` indicates an open price
 pub trait Open {
     fn open(&self) -> f64;
@@ -26,9 +46,9 @@ pub trait Low {
     fn low(&self) -> f64;
 }
 
-/// trait `Volume` indicates a volume
+/// trait `Volume` indicates a traded volume, which may be fractional
 pub trait Volume {
-    fn volume(&self) -> u64;
+    fn volume(&self) -> f64;
 }
 
 impl Close for f64 {
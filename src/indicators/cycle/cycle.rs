@@ -0,0 +1,3 @@
+/// trait `Cycle<T>` marks John Ehlers' signal processing indicators, which filter or measure the
+/// cycles in a series
+pub trait Cycle<T>: crate::Indicator<T> {}
@@ -0,0 +1,101 @@
+use crate::{indicators::cycle::hilbert::Hilbert, *};
+use anyhow::Error;
+
+pub struct DominantCycleFactory {}
+
+impl DominantCycleFactory {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn build(self) -> Result<DominantCycle, Error> {
+        Ok(DominantCycle {
+            hilbert: Hilbert::new(),
+        })
+    }
+}
+
+/// Dominant cycle period in bars, measured by John Ehlers' Hilbert transform homodyne
+/// discriminator and limited to between 6 and 50 bars. The output is zero for the first five
+/// bars, and needs about 50 bars to settle
+#[derive(Clone, Debug)]
+pub struct DominantCycle {
+    hilbert: Hilbert,
+}
+
+impl<T: Close> Indicator<T> for DominantCycle {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.hilbert.push(next.close());
+        self.hilbert.smooth_period()
+    }
+}
+
+impl<T: Close> indicators::Cycle<T> for DominantCycle {}
+
+impl DominantCycle {
+    pub fn factory() -> DominantCycleFactory {
+        DominantCycleFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{helpers::ehlers::price, indicators::DominantCycle, Indicator};
+
+    #[test]
+    fn test_build() {
+        DominantCycle::factory().build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dominant_cycle = DominantCycle::factory().build().unwrap();
+
+        let periods: Vec<f64> = (0..150).map(|i| dominant_cycle.next(price(i))).collect();
+        assert_eq!(periods[4], 0.0);
+        // the homodyne discriminator listing in John Ehlers' "Rocket Science for Traders" (Wiley,
+        // 2001)
+        for (i, period) in [
+            (59, 21.727579021288953),
+            (69, 21.551073684116016),
+            (79, 19.575849566917736),
+            (89, 18.78492960786376),
+            (99, 21.077513828374308),
+            (109, 21.766702209380252),
+            (119, 20.476876984258258),
+            (129, 18.59920876640248),
+            (139, 20.294752391919133),
+            (149, 21.67741680924637),
+        ] {
+            assert!(
+                (periods[i] - period).abs() < 1e-9,
+                "period at bar {}: {}",
+                i,
+                periods[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_sine() {
+        for period in [20.0, 30.0] {
+            let mut dominant_cycle = DominantCycle::factory().build().unwrap();
+            for i in 0..300 {
+                let detected =
+                    dominant_cycle.next(100.0 + 10.0 * (2.0 * PI * i as f64 / period).sin());
+                if i >= 100 {
+                    assert!(
+                        (detected - period).abs() < 0.1,
+                        "period {} at bar {}: {}",
+                        period,
+                        i,
+                        detected
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    indicators::{RollingMax, RollingMin},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct FisherTransformFactory {
+    window_size: usize,
+}
+
+/// The Fisher transform of the median price, and its previous value as a trigger line
+#[derive(Clone, PartialEq, Debug)]
+pub struct FisherTransformResult {
+    pub fisher: f64,
+    pub trigger: f64,
+}
+
+/// John Ehlers' Fisher transform. The median price is scaled to its range over the window,
+/// smoothed, and mapped through the Fisher transform so that turning points show as sharp peaks
+#[derive(Clone, Debug)]
+pub struct FisherTransform {
+    max: RollingMax,
+    min: RollingMin,
+    value: f64,
+    fisher: f64,
+}
+
+impl FisherTransformFactory {
+    pub fn new() -> Self {
+        Self { window_size: 10 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<FisherTransform, Error> {
+        check_window_size(self.window_size)?;
+        Ok(FisherTransform {
+            max: RollingMax::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+            min: RollingMin::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+            value: 0.0,
+            fisher: 0.0,
+        })
+    }
+}
+
+impl<T: High + Low> Indicator<T> for FisherTransform {
+    type Output = FisherTransformResult;
+    fn next(&mut self, next: T) -> Self::Output {
+        let price = (next.high() + next.low()) / 2.0;
+        let max = self.max.next(price);
+        let min = self.min.next(price);
+        let position = if max > min {
+            (price - min) / (max - min) - 0.5
+        } else {
+            0.0
+        };
+        // Ehlers limits the value to just inside (-1, 1) so the transform stays finite
+        self.value = 0.66 * position + 0.67 * self.value;
+        if self.value > 0.99 {
+            self.value = 0.999;
+        } else if self.value < -0.99 {
+            self.value = -0.999;
+        }
+
+        let trigger = self.fisher;
+        self.fisher = 0.5 * ((1.0 + self.value) / (1.0 - self.value)).ln() + 0.5 * trigger;
+        FisherTransformResult {
+            fisher: self.fisher,
+            trigger,
+        }
+    }
+}
+
+impl<T: High + Low> indicators::Cycle<T> for FisherTransform {}
+
+impl FisherTransform {
+    pub fn factory() -> FisherTransformFactory {
+        FisherTransformFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ehlers::price,
+        indicators::{FisherTransform, FisherTransformResult},
+        *,
+    };
+
+    #[derive(Clone, Debug)]
+    struct Hl {
+        high: f64,
+        low: f64,
+    }
+
+    impl High for Hl {
+        fn high(&self) -> f64 {
+            self.high
+        }
+    }
+
+    impl Low for Hl {
+        fn low(&self) -> f64 {
+            self.low
+        }
+    }
+
+    fn bar(i: usize) -> Hl {
+        Hl {
+            high: price(i) + 1.0,
+            low: price(i) - 1.0,
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        FisherTransform::factory()
+            .with_window_size(9)
+            .build()
+            .unwrap();
+        assert!(FisherTransform::factory()
+            .with_window_size(1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut fisher = FisherTransform::factory().build().unwrap();
+
+        let results: Vec<FisherTransformResult> = (0..150).map(|i| fisher.next(bar(i))).collect();
+        assert_eq!(
+            results[0],
+            FisherTransformResult {
+                fisher: 0.0,
+                trigger: 0.0
+            }
+        );
+        // the listing in John Ehlers' "Using the Fisher Transform", Technical Analysis of Stocks &
+        // Commodities (November 2002), which starts once its window is full. The partial windows
+        // before then are forgotten by the smoothing well within the first 69 bars
+        for (i, fish, trigger) in [
+            (69, 1.4123495371791162, 2.0149574642039303),
+            (79, -0.3832135826452706, -1.1413693904857358),
+            (89, 1.3430362134915899, 2.1953600082908364),
+            (99, -2.2123115116560808, -2.7895566086006944),
+            (109, 1.9577553064336883, 2.450445503662205),
+            (119, -0.44968720096974923, -1.184776839175787),
+            (129, 0.9411165899381876, 1.8430100060552117),
+            (139, -1.993680176426432, -2.680521531899487),
+            (149, 2.2828569442877287, 2.74418488405753),
+        ] {
+            assert!(
+                (results[i].fisher - fish).abs() < 1e-9,
+                "fisher at bar {}: {:?}",
+                i,
+                results[i]
+            );
+            assert!(
+                (results[i].trigger - trigger).abs() < 1e-9,
+                "trigger at bar {}: {:?}",
+                i,
+                results[i]
+            );
+        }
+    }
+}
@@ -0,0 +1,100 @@
+/// The homodyne discriminator from John Ehlers' "Rocket Science for Traders", shared by
+/// `DominantCycle` and `SineWave`. As in Ehlers' code, the calculation starts on the sixth bar
+/// and every earlier value is taken as zero
+#[derive(Clone, Debug)]
+pub(crate) struct Hilbert {
+    count: usize,
+    prices: [f64; 4],
+    // the sine wave sums over up to 50 bars of smoothed prices
+    smooth: [f64; 50],
+    detrender: [f64; 10],
+    q1: [f64; 7],
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+}
+
+fn shift<const N: usize>(history: &mut [f64; N], value: f64) {
+    history.rotate_right(1);
+    history[0] = value;
+}
+
+// the Hilbert transform of the value at `history[0]`, reading every other bar
+fn transform(history: &[f64]) -> f64 {
+    0.0962 * history[0] + 0.5769 * history[2] - 0.5769 * history[4] - 0.0962 * history[6]
+}
+
+impl Hilbert {
+    pub(crate) fn new() -> Self {
+        Self {
+            count: 0,
+            prices: [0.0; 4],
+            smooth: [0.0; 50],
+            detrender: [0.0; 10],
+            q1: [0.0; 7],
+            i2: 0.0,
+            q2: 0.0,
+            re: 0.0,
+            im: 0.0,
+            period: 0.0,
+            smooth_period: 0.0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, price: f64) {
+        shift(&mut self.prices, price);
+        self.count = (self.count + 1).min(6);
+        if !self.started() {
+            return;
+        }
+        let [p0, p1, p2, p3] = self.prices;
+        shift(
+            &mut self.smooth,
+            (4.0 * p0 + 3.0 * p1 + 2.0 * p2 + p3) / 10.0,
+        );
+
+        let adjustment = 0.075 * self.period + 0.54;
+        shift(&mut self.detrender, transform(&self.smooth) * adjustment);
+        shift(&mut self.q1, transform(&self.detrender) * adjustment);
+
+        // advance the phases of the in phase and quadrature components by 90 degrees
+        let i1 = self.detrender[3];
+        let ji = transform(&self.detrender[3..]) * adjustment;
+        let jq = transform(&self.q1) * adjustment;
+        let i2 = 0.2 * (i1 - jq) + 0.8 * self.i2;
+        let q2 = 0.2 * (self.q1[0] + ji) + 0.8 * self.q2;
+
+        self.re = 0.2 * (i2 * self.i2 + q2 * self.q2) + 0.8 * self.re;
+        self.im = 0.2 * (i2 * self.q2 - q2 * self.i2) + 0.8 * self.im;
+        self.i2 = i2;
+        self.q2 = q2;
+
+        let mut period = self.period;
+        if self.im != 0.0 && self.re != 0.0 {
+            period = 360.0 / (self.im / self.re).atan().to_degrees();
+        }
+        period = period
+            .min(1.5 * self.period)
+            .max(0.67 * self.period)
+            .clamp(6.0, 50.0);
+        self.period = 0.2 * period + 0.8 * self.period;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+    }
+
+    /// whether the discriminator has started, which it does on the sixth bar
+    pub(crate) fn started(&self) -> bool {
+        self.count == 6
+    }
+
+    /// smoothed prices, newest first
+    pub(crate) fn smooth(&self) -> &[f64] {
+        &self.smooth
+    }
+
+    pub(crate) fn smooth_period(&self) -> f64 {
+        self.smooth_period
+    }
+}
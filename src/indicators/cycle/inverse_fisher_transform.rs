@@ -0,0 +1,120 @@
+use crate::{indicators::WMA, *};
+use anyhow::Error;
+
+pub struct InverseFisherTransformFactory {
+    window_size: usize,
+    center: f64,
+    scale: f64,
+}
+
+/// John Ehlers' inverse Fisher transform. The input is centred, scaled, smoothed with a WMA and
+/// passed through `tanh`, which pushes an oscillator towards -1 and 1 and makes its crossings
+/// decisive. The defaults suit an RSI; for a CCI use a centre of zero and a scale of about 0.03
+#[derive(Clone, Debug)]
+pub struct InverseFisherTransform {
+    center: f64,
+    scale: f64,
+    wma: WMA,
+}
+
+impl InverseFisherTransformFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 9,
+            center: 50.0,
+            scale: 0.1,
+        }
+    }
+
+    /// the window of the WMA smoothing the scaled input
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_center(mut self, center: f64) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn build(self) -> Result<InverseFisherTransform, Error> {
+        Ok(InverseFisherTransform {
+            center: self.center,
+            scale: self.scale,
+            wma: WMA::factory().with_window_size(self.window_size).build()?,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for InverseFisherTransform {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.wma
+            .next(self.scale * (next.close() - self.center))
+            .tanh()
+    }
+}
+
+impl<T: Close> indicators::Cycle<T> for InverseFisherTransform {}
+
+impl InverseFisherTransform {
+    pub fn factory() -> InverseFisherTransformFactory {
+        InverseFisherTransformFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::{indicators::InverseFisherTransform, Indicator};
+
+    fn oscillator(i: usize) -> f64 {
+        let i = i as f64;
+        50.0 + 30.0 * (2.0 * PI * i / 15.0).sin() + 10.0 * (2.0 * PI * i / 7.0).sin()
+    }
+
+    #[test]
+    fn test_build() {
+        InverseFisherTransform::factory()
+            .with_window_size(5)
+            .with_center(0.0)
+            .with_scale(0.03)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut inverse_fisher = InverseFisherTransform::factory().build().unwrap();
+
+        let results: Vec<f64> = (0..40)
+            .map(|i| inverse_fisher.next(oscillator(i)))
+            .collect();
+        // the listing in John Ehlers' "The Inverse Fisher Transform", Technical Analysis of Stocks
+        // & Commodities (May 2004), with this oscillator in place of its RSI and started once the
+        // nine bar weighted average is full
+        for (i, expected) in [
+            (8, 0.8831029161975827),
+            (12, -0.9134048810135353),
+            (16, -0.725053144788591),
+            (20, 0.8986063917370264),
+            (24, 0.7438682178161055),
+            (28, -0.9651545274392055),
+            (32, -0.2474053375607741),
+            (36, 0.9567775200414914),
+        ] {
+            assert!(
+                (results[i] - expected).abs() < 1e-9,
+                "bar {}: {}",
+                i,
+                results[i]
+            );
+        }
+    }
+}
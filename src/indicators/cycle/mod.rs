@@ -0,0 +1,20 @@
+#[allow(clippy::module_inception)]
+mod cycle;
+pub use cycle::Cycle;
+
+mod dominant_cycle;
+pub use dominant_cycle::DominantCycle;
+
+mod fisher_transform;
+pub use fisher_transform::{FisherTransform, FisherTransformResult};
+
+mod hilbert;
+
+mod inverse_fisher_transform;
+pub use inverse_fisher_transform::InverseFisherTransform;
+
+mod roofing_filter;
+pub use roofing_filter::RoofingFilter;
+
+mod sine_wave;
+pub use sine_wave::{SineWave, SineWaveResult};
@@ -0,0 +1,155 @@
+use crate::{indicators::SuperSmoother, *};
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct RoofingFilterFactory {
+    high_pass_period: usize,
+    low_pass_period: usize,
+}
+
+/// John Ehlers' roofing filter. A two pole high pass filter removes cycles longer than the high
+/// pass period, then a `SuperSmoother` removes those shorter than the low pass period, leaving a
+/// zero mean oscillator of the cycles in between. The first two outputs are zero
+#[derive(Clone, Debug)]
+pub struct RoofingFilter {
+    a: f64,
+    b: f64,
+    c: f64,
+    count: usize,
+    closes: [f64; 2],
+    high_pass: [f64; 2],
+    super_smoother: SuperSmoother,
+}
+
+impl RoofingFilterFactory {
+    pub fn new() -> Self {
+        Self {
+            high_pass_period: 48,
+            low_pass_period: 10,
+        }
+    }
+
+    pub fn with_high_pass_period(mut self, high_pass_period: usize) -> Self {
+        self.high_pass_period = high_pass_period;
+        self
+    }
+
+    pub fn with_low_pass_period(mut self, low_pass_period: usize) -> Self {
+        self.low_pass_period = low_pass_period;
+        self
+    }
+
+    pub fn build(self) -> Result<RoofingFilter, Error> {
+        check_window_size(self.low_pass_period)?;
+        if self.high_pass_period <= self.low_pass_period {
+            return Err(anyhow!(
+                "High pass period must be greater than the low pass period. You used {} and {}",
+                self.high_pass_period,
+                self.low_pass_period
+            ));
+        }
+        // Ehlers' published code rounds the root half to 0.707
+        let angle = (0.707 * 360.0 / self.high_pass_period as f64).to_radians();
+        let alpha = (angle.cos() + angle.sin() - 1.0) / angle.cos();
+        Ok(RoofingFilter {
+            a: (1.0 - alpha / 2.0).powi(2),
+            b: 2.0 * (1.0 - alpha),
+            c: -(1.0 - alpha).powi(2),
+            count: 0,
+            closes: [0.0; 2],
+            high_pass: [0.0; 2],
+            super_smoother: SuperSmoother::factory()
+                .with_window_size(self.low_pass_period)
+                .build()?,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for RoofingFilter {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        let close = next.close();
+        let high_pass = if self.count < 2 {
+            self.count += 1;
+            0.0
+        } else {
+            self.a * (close - 2.0 * self.closes[0] + self.closes[1])
+                + self.b * self.high_pass[0]
+                + self.c * self.high_pass[1]
+        };
+        self.closes = [close, self.closes[0]];
+        self.high_pass = [high_pass, self.high_pass[0]];
+        self.super_smoother.next(high_pass)
+    }
+}
+
+impl<T: Close> indicators::Cycle<T> for RoofingFilter {}
+
+impl RoofingFilter {
+    pub fn factory() -> RoofingFilterFactory {
+        RoofingFilterFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{helpers::ehlers::price, indicators::RoofingFilter, Indicator};
+
+    #[test]
+    fn test_build() {
+        RoofingFilter::factory()
+            .with_high_pass_period(40)
+            .with_low_pass_period(8)
+            .build()
+            .unwrap();
+        assert!(RoofingFilter::factory()
+            .with_high_pass_period(10)
+            .with_low_pass_period(10)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut roofing = RoofingFilter::factory().build().unwrap();
+
+        let filtered: Vec<f64> = (0..150).map(|i| roofing.next(price(i))).collect();
+        assert_eq!(filtered[..2], [0.0, 0.0]);
+        // the roofing filter listing in John Ehlers' "Cycle Analytics for Traders" (Wiley, 2013),
+        // which starts on the third bar
+        for (i, expected) in [
+            (59, -7.912747808809011),
+            (69, 2.0632539235177987),
+            (79, 0.42194300035678967),
+            (89, 2.783137408994703),
+            (99, -7.00065151368834),
+            (109, 4.455551338917759),
+            (119, 0.22665691034487767),
+            (129, 1.0494924001574764),
+            (139, -6.090334567826493),
+            (149, 6.079925584488716),
+        ] {
+            assert!(
+                (filtered[i] - expected).abs() < 1e-9,
+                "bar {}: {}",
+                i,
+                filtered[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_response() {
+        let mut flat = RoofingFilter::factory().build().unwrap();
+        let mut alternating = RoofingFilter::factory().build().unwrap();
+
+        // the high pass removes a flat price and the SuperSmoother a two bar cycle
+        for i in 0..300 {
+            assert_eq!(flat.next(100.0), 0.0);
+            let filtered = alternating.next(if i % 2 == 0 { 101.0 } else { 99.0 });
+            if i >= 250 {
+                assert!(filtered.abs() < 1e-6, "bar {}: {}", i, filtered);
+            }
+        }
+    }
+}
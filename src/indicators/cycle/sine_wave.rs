@@ -0,0 +1,141 @@
+use crate::{indicators::cycle::hilbert::Hilbert, *};
+use anyhow::Error;
+
+pub struct SineWaveFactory {}
+
+impl SineWaveFactory {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn build(self) -> Result<SineWave, Error> {
+        Ok(SineWave {
+            hilbert: Hilbert::new(),
+            phase: 0.0,
+        })
+    }
+}
+
+/// The sine of the dominant cycle phase, and the sine 45 degrees ahead of it. The lines cross
+/// at cycle turning points and run parallel while the market trends
+#[derive(Clone, PartialEq, Debug)]
+pub struct SineWaveResult {
+    pub sine: f64,
+    pub lead_sine: f64,
+}
+
+/// John Ehlers' sine wave indicator. The phase of the dominant cycle is found by correlating one
+/// dominant cycle period of smoothed prices with a sine and a cosine. Both lines are zero for
+/// the first five bars, and need about 50 bars to settle
+#[derive(Clone, Debug)]
+pub struct SineWave {
+    hilbert: Hilbert,
+    phase: f64,
+}
+
+impl<T: Close> Indicator<T> for SineWave {
+    type Output = SineWaveResult;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.hilbert.push(next.close());
+        if !self.hilbert.started() {
+            return SineWaveResult {
+                sine: 0.0,
+                lead_sine: 0.0,
+            };
+        }
+        let smooth_period = self.hilbert.smooth_period();
+        let cycle_period = (smooth_period + 0.5) as usize;
+
+        let (mut real, mut imaginary) = (0.0, 0.0);
+        for (count, smooth) in self.hilbert.smooth().iter().take(cycle_period).enumerate() {
+            let angle = 2.0 * std::f64::consts::PI * count as f64 / cycle_period as f64;
+            real += angle.sin() * smooth;
+            imaginary += angle.cos() * smooth;
+        }
+
+        let mut phase = self.phase;
+        if imaginary.abs() > 0.0 {
+            phase = (real / imaginary).atan().to_degrees();
+        }
+        if imaginary.abs() <= 0.001 && real != 0.0 {
+            phase += 90.0 * real.signum();
+        }
+        // compensate for the lag of the smoothing
+        phase += 90.0 + 360.0 / smooth_period;
+        if imaginary < 0.0 {
+            phase += 180.0;
+        }
+        if phase > 315.0 {
+            phase -= 360.0;
+        }
+        self.phase = phase;
+
+        SineWaveResult {
+            sine: phase.to_radians().sin(),
+            lead_sine: (phase + 45.0).to_radians().sin(),
+        }
+    }
+}
+
+impl<T: Close> indicators::Cycle<T> for SineWave {}
+
+impl SineWave {
+    pub fn factory() -> SineWaveFactory {
+        SineWaveFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ehlers::price,
+        indicators::{SineWave, SineWaveResult},
+        Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        SineWave::factory().build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sine_wave = SineWave::factory().build().unwrap();
+
+        let results: Vec<SineWaveResult> = (0..150).map(|i| sine_wave.next(price(i))).collect();
+        assert_eq!(
+            results[4],
+            SineWaveResult {
+                sine: 0.0,
+                lead_sine: 0.0
+            }
+        );
+        // the sine wave indicator listing in John Ehlers' "Rocket Science for Traders" (Wiley,
+        // 2001)
+        for (i, sine, lead_sine) in [
+            (59, -0.7161040105937974, -0.012806004083831434),
+            (69, 0.9078842320263348, 0.3455371066748935),
+            (79, -0.2402342295519476, 0.5165278712455288),
+            (89, -0.07342449369854492, -0.7571171010885326),
+            (99, -0.45451096837377086, 0.30846131296734414),
+            (109, 0.864378751076085, 0.2556455821117823),
+            (119, -0.5005916884415639, 0.25815891175545935),
+            (129, -0.10601602930080636, -0.7780864782148067),
+            (139, -0.20463539243143838, 0.5474441037849156),
+            (149, 0.7763737327128197, 0.10330894638445945),
+        ] {
+            assert!(
+                (results[i].sine - sine).abs() < 1e-9,
+                "sine at bar {}: {:?}",
+                i,
+                results[i]
+            );
+            assert!(
+                (results[i].lead_sine - lead_sine).abs() < 1e-9,
+                "lead sine at bar {}: {:?}",
+                i,
+                results[i]
+            );
+        }
+    }
+}
@@ -1,6 +1,14 @@
+mod cycle;
 mod moving_average;
+mod pair;
+mod patterns;
+mod statistics;
 mod trend;
 mod volatility;
+pub use cycle::*;
 pub use moving_average::*;
+pub use pair::*;
+pub use patterns::*;
+pub use statistics::*;
 pub use trend::*;
 pub use volatility::*;
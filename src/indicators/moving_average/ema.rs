@@ -12,6 +12,9 @@ pub struct EMA {
     window_size: usize,
     k: f64,
     prev_ema: f64,
+    // exponentially weighted variance, and the sum of the squared weights for its bias correction
+    variance: f64,
+    weight_squares: f64,
     is_new: bool,
 }
 
@@ -42,6 +45,8 @@ impl EmaFactory {
             window_size: self.window_size,
             k: self.smoothing / (self.window_size as f64 + 1.0),
             prev_ema: 0.0,
+            variance: 0.0,
+            weight_squares: 1.0,
             is_new: true,
         })
     }
@@ -54,6 +59,9 @@ impl<T: Close> crate::Indicator<T> for EMA {
             self.is_new = false;
             self.prev_ema = next.close();
         } else {
+            let diff = next.close() - self.prev_ema;
+            self.variance = (1.0 - self.k) * (self.variance + self.k * diff * diff);
+            self.weight_squares = (1.0 - self.k).powi(2) * self.weight_squares + self.k * self.k;
             self.prev_ema = self.k * next.close() + (1.0 - self.k) * self.prev_ema;
         }
         self.prev_ema
@@ -64,6 +72,16 @@ impl<T: Close> crate::indicators::MovingAverage<T> for EMA {
     fn window_size(&self) -> usize {
         self.window_size
     }
+
+    fn variance(&self, kind: &indicators::VarianceKind) -> Option<f64> {
+        match kind {
+            indicators::VarianceKind::Population => Some(self.variance.max(0.0)),
+            indicators::VarianceKind::Sample if self.weight_squares >= 1.0 => Some(0.0),
+            indicators::VarianceKind::Sample => {
+                Some((self.variance / (1.0 - self.weight_squares)).max(0.0))
+            }
+        }
+    }
 }
 
 impl EMA {
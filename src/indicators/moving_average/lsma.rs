@@ -0,0 +1,94 @@
+use crate::{indicators::LinearRegression, *};
+use anyhow::Error;
+
+pub struct LsmaFactory {
+    window_size: usize,
+}
+
+/// Least squares moving average: the end point of a rolling linear regression
+#[derive(Clone, Debug)]
+pub struct LSMA {
+    regression: LinearRegression,
+}
+
+impl LsmaFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<LSMA, Error> {
+        Ok(LSMA {
+            regression: LinearRegression::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for LSMA {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.regression.next(next).value
+    }
+}
+
+impl<T: Close> indicators::MovingAverage<T> for LSMA {
+    fn window_size(&self) -> usize {
+        self.regression.window_size()
+    }
+}
+
+impl LSMA {
+    pub fn factory() -> LsmaFactory {
+        LsmaFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::BollingerBand, indicators::LSMA, indicators::MACD, Indicator};
+
+    #[test]
+    fn test_build() {
+        LSMA::factory().with_window_size(5).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lsma = LSMA::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(lsma.next(10.0), 10.0);
+        assert_eq!(lsma.next(20.0), 20.0);
+        assert_eq!(lsma.next(15.0), 17.5);
+        assert_eq!(lsma.next(10.0), 13.0);
+        assert_eq!(lsma.next(10.0), 11.0);
+        assert_eq!(lsma.next(10.0), 8.0);
+        assert_eq!(lsma.next(12.5), 10.5);
+    }
+
+    #[test]
+    fn test_composite() {
+        let mut macd = MACD::<LSMA, LSMA, LSMA>::factory()
+            .with_fast_ma(LSMA::factory().with_window_size(3).build().unwrap())
+            .with_slow_ma(LSMA::factory().with_window_size(6).build().unwrap())
+            .with_signal_ma(LSMA::factory().with_window_size(3).build().unwrap())
+            .build()
+            .unwrap();
+        let mut bollinger_band = BollingerBand::<LSMA>::factory()
+            .with_moving_average(LSMA::factory().with_window_size(5).build().unwrap())
+            .build()
+            .unwrap();
+
+        for close in 0..10 {
+            let close = close as f64;
+            // both averages track a straight line exactly
+            assert_eq!(macd.next(close).macd, 0.0);
+            assert_eq!(bollinger_band.next(close).middle, close);
+        }
+    }
+}
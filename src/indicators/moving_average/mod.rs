@@ -1,8 +1,18 @@
 mod ema;
 pub use ema::EMA;
 
+mod lsma;
+pub use lsma::LSMA;
+
+#[allow(clippy::module_inception)]
 mod moving_average;
-pub use moving_average::MovingAverage;
+pub use moving_average::{MovingAverage, VarianceKind};
 
 mod sma;
 pub use sma::SMA;
+
+mod super_smoother;
+pub use super_smoother::SuperSmoother;
+
+mod wma;
+pub use wma::WMA;
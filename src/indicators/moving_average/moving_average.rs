@@ -1,4 +1,22 @@
 use crate::*;
+
+/// whether a variance divides by the total weight, or is corrected for bias. The sample variance
+/// scales the population one by `1 / (1 - Σw² / (Σw)²)` for the weights of the average, which is
+/// `n / (n - 1)` for equal weights. Averages that don't track their own variance have no weights
+/// to go on, and `SD` scales theirs by `n / (n - 1)`
+#[derive(Clone, Debug, PartialEq)]
+pub enum VarianceKind {
+    Population,
+    Sample,
+}
+
 pub trait MovingAverage<T: Close>: crate::Indicator<T, Output = f64> {
     fn window_size(&self) -> usize;
+
+    /// the variance of the inputs seen so far, weighted the same way as the average. Returns
+    /// `None` for averages that don't track it, in which case `SD` estimates it from a second
+    /// average of squares
+    fn variance(&self, _kind: &VarianceKind) -> Option<f64> {
+        None
+    }
 }
@@ -1,7 +1,7 @@
 use crate::helpers::check_window_size;
 use anyhow::Error;
 
-use crate::Close;
+use crate::{indicators::VarianceKind, Close};
 
 pub struct SmaFactory {
     window_size: usize,
@@ -22,6 +22,8 @@ impl SmaFactory {
             count: 0,
             deque: vec![0.0; self.window_size].into_boxed_slice(),
             sum: 0.0,
+            mean: 0.0,
+            m2: 0.0,
         })
     }
 }
@@ -30,12 +32,37 @@ impl<T: crate::Close> crate::indicators::MovingAverage<T> for SMA {
     fn window_size(&self) -> usize {
         self.period
     }
+
+    fn variance(&self, kind: &VarianceKind) -> Option<f64> {
+        let divisor = match kind {
+            VarianceKind::Population => self.count as f64,
+            VarianceKind::Sample => self.count as f64 - 1.0,
+        };
+        if divisor <= 0.0 {
+            return Some(0.0);
+        }
+        Some((self.m2 / divisor).max(0.0))
+    }
 }
 
 impl SMA {
     pub fn factory() -> SmaFactory {
         SmaFactory::new()
     }
+
+    pub(crate) fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    // The running sums pick up rounding error on every update, so they are rebuilt from the
+    // window each time it wraps around. This keeps the error bounded by a single window of
+    // updates. Each rebuild is two passes over the window once every `period` inputs, which
+    // amortises to an addition and a squared deviation per input
+    fn recompute(&mut self) {
+        self.sum = self.deque.iter().sum();
+        self.mean = self.sum / self.period as f64;
+        self.m2 = self.deque.iter().map(|x| (x - self.mean).powi(2)).sum();
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -44,14 +71,18 @@ pub struct SMA {
     index: usize,
     count: usize,
     sum: f64,
+    // Welford state of the window, used for its variance
+    mean: f64,
+    m2: f64,
     deque: Box<[f64]>,
 }
 
 impl<T: Close> crate::Indicator<T> for SMA {
     type Output = f64;
     fn next(&mut self, input: T) -> Self::Output {
+        let new = input.close();
         let old_val = self.deque[self.index];
-        self.deque[self.index] = input.close();
+        self.deque[self.index] = new;
         self.index = if self.index + 1 < self.period {
             self.index + 1
         } else {
@@ -59,8 +90,18 @@ impl<T: Close> crate::Indicator<T> for SMA {
         };
         if self.count < self.period {
             self.count += 1;
+            let delta = new - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (new - self.mean);
+        } else {
+            let prev_mean = self.mean;
+            self.mean += (new - old_val) / self.period as f64;
+            self.m2 += (new - old_val) * (new - self.mean + old_val - prev_mean);
+        }
+        self.sum = self.sum - old_val + new;
+        if self.index == 0 && self.count == self.period {
+            self.recompute();
         }
-        self.sum = self.sum - old_val + input.close();
         self.sum / (self.count as f64)
     }
 }
@@ -85,4 +126,25 @@ mod tests {
         assert_eq!(sma.next(10.0), 13.0);
         assert_eq!(sma.next(12.5), 11.5);
     }
+
+    #[test]
+    fn test_long_run() {
+        let mut sma = SMA::factory().with_window_size(14).build().unwrap();
+
+        // xorshift, so the rounding errors don't cancel out over a repeating pattern
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut window = [0.0; 14];
+        let mut result = 0.0;
+        for i in 0..100_000_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let value = 1e6 + (state % 1_000_000) as f64 * 1e-3;
+            window[i % 14] = value;
+            result = sma.next(value);
+        }
+
+        let expected = window.iter().sum::<f64>() / 14.0;
+        assert!((result - expected).abs() <= expected * 1e-14);
+    }
 }
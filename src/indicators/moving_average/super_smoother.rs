@@ -0,0 +1,140 @@
+use crate::helpers::check_window_size;
+use anyhow::Error;
+
+use crate::Close;
+
+pub struct SuperSmootherFactory {
+    window_size: usize,
+}
+
+impl SuperSmootherFactory {
+    pub fn new() -> Self {
+        Self { window_size: 10 }
+    }
+    /// the critical period of the filter. Cycles shorter than this are attenuated
+    pub fn with_window_size(self, window_size: usize) -> Self {
+        Self { window_size }
+    }
+    pub fn build(self) -> Result<SuperSmoother, Error> {
+        check_window_size(self.window_size)?;
+        let period = self.window_size as f64;
+        // Ehlers' published code rounds root two and pi, and these keep outputs matching it
+        #[allow(clippy::approx_constant)]
+        let a1 = (-1.414 * 3.14159 / period).exp();
+        let c2 = 2.0 * a1 * (1.414 * 180.0 / period).to_radians().cos();
+        let c3 = -a1 * a1;
+        Ok(SuperSmoother {
+            period: self.window_size,
+            c1: 1.0 - c2 - c3,
+            c2,
+            c3,
+            count: 0,
+            prev_input: 0.0,
+            filt: [0.0; 2],
+        })
+    }
+}
+
+impl<T: crate::Close> crate::indicators::MovingAverage<T> for SuperSmoother {
+    fn window_size(&self) -> usize {
+        self.period
+    }
+}
+
+impl SuperSmoother {
+    pub fn factory() -> SuperSmootherFactory {
+        SuperSmootherFactory::new()
+    }
+}
+
+/// John Ehlers' two pole SuperSmoother filter, a Butterworth low pass filter averaging the last
+/// two inputs. The first two outputs are the inputs themselves
+#[derive(Clone, Debug)]
+pub struct SuperSmoother {
+    period: usize,
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    count: usize,
+    prev_input: f64,
+    filt: [f64; 2],
+}
+
+impl<T: Close> crate::Indicator<T> for SuperSmoother {
+    type Output = f64;
+    fn next(&mut self, input: T) -> Self::Output {
+        let new = input.close();
+        let filt = if self.count < 2 {
+            self.count += 1;
+            new
+        } else {
+            self.c1 * (new + self.prev_input) / 2.0
+                + self.c2 * self.filt[0]
+                + self.c3 * self.filt[1]
+        };
+        self.prev_input = new;
+        self.filt = [filt, self.filt[0]];
+        filt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{helpers::ehlers::price, indicators::SuperSmoother, Indicator};
+
+    #[test]
+    fn test_build() {
+        SuperSmoother::factory()
+            .with_window_size(5)
+            .build()
+            .unwrap();
+        assert!(SuperSmoother::factory()
+            .with_window_size(1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut super_smoother = SuperSmoother::factory().build().unwrap();
+
+        let filtered: Vec<f64> = (0..150).map(|i| super_smoother.next(price(i))).collect();
+        assert_eq!(filtered[0], price(0));
+        assert_eq!(filtered[1], price(1));
+        // the SuperSmoother listing in John Ehlers' "Cycle Analytics for Traders" (Wiley, 2013)
+        for (i, expected) in [
+            (59, 93.72156875416962),
+            (69, 106.55918612343977),
+            (79, 98.59480273439553),
+            (89, 116.08350126722951),
+            (99, 93.55608890531035),
+            (109, 110.09323465273141),
+            (119, 102.37057958404145),
+            (129, 116.09834213034004),
+            (139, 94.28461322650108),
+            (149, 114.4013939267879),
+        ] {
+            assert!(
+                (filtered[i] - expected).abs() < 1e-9,
+                "bar {}: {}",
+                i,
+                filtered[i]
+            );
+        }
+    }
+
+    #[test]
+    fn test_response() {
+        let mut flat = SuperSmoother::factory().build().unwrap();
+        let mut alternating = SuperSmoother::factory().build().unwrap();
+
+        // unit gain on a flat price, and none at all on a two bar cycle
+        for i in 0..150 {
+            assert_eq!(flat.next(100.0), 100.0);
+            let filtered = alternating.next(if i % 2 == 0 { 101.0 } else { 99.0 });
+            if i >= 100 {
+                assert!((filtered - 100.0).abs() < 1e-9, "bar {}: {}", i, filtered);
+            }
+        }
+    }
+}
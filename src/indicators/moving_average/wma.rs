@@ -0,0 +1,128 @@
+use crate::helpers::check_window_size;
+use anyhow::Error;
+
+use crate::{indicators::VarianceKind, Close};
+
+pub struct WmaFactory {
+    window_size: usize,
+}
+
+impl WmaFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+    pub fn with_window_size(self, window_size: usize) -> Self {
+        Self { window_size }
+    }
+    pub fn build(self) -> Result<WMA, Error> {
+        check_window_size(self.window_size)?;
+        Ok(WMA {
+            period: self.window_size,
+            index: 0,
+            count: 0,
+            deque: vec![0.0; self.window_size].into_boxed_slice(),
+            sum: 0.0,
+            weighted_sum: 0.0,
+        })
+    }
+}
+
+impl<T: crate::Close> crate::indicators::MovingAverage<T> for WMA {
+    fn window_size(&self) -> usize {
+        self.period
+    }
+
+    // two passes over the window, which stay accurate however large the values are
+    fn variance(&self, kind: &VarianceKind) -> Option<f64> {
+        let count = self.count as f64;
+        let total = count * (count + 1.0) / 2.0;
+        let mean = self.window().map(|(weight, x)| weight * x).sum::<f64>() / total;
+        let variance = self
+            .window()
+            .map(|(weight, x)| weight * (x - mean).powi(2))
+            .sum::<f64>()
+            / total;
+        match kind {
+            VarianceKind::Population => Some(variance),
+            VarianceKind::Sample if self.count < 2 => Some(0.0),
+            VarianceKind::Sample => {
+                let weight_squares =
+                    count * (count + 1.0) * (2.0 * count + 1.0) / 6.0 / total.powi(2);
+                Some(variance / (1.0 - weight_squares))
+            }
+        }
+    }
+}
+
+impl WMA {
+    pub fn factory() -> WmaFactory {
+        WmaFactory::new()
+    }
+
+    // the values in the window with their weights, oldest first
+    fn window(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        (0..self.count).map(|k| {
+            let position = (self.index + self.period - self.count + k) % self.period;
+            ((k + 1) as f64, self.deque[position])
+        })
+    }
+}
+
+/// Linearly weighted moving average. The newest value has weight `window_size`, the oldest has
+/// weight one
+#[derive(Clone, Debug)]
+pub struct WMA {
+    period: usize,
+    index: usize,
+    count: usize,
+    sum: f64,
+    weighted_sum: f64,
+    deque: Box<[f64]>,
+}
+
+impl<T: Close> crate::Indicator<T> for WMA {
+    type Output = f64;
+    fn next(&mut self, input: T) -> Self::Output {
+        let new = input.close();
+        let old_val = self.deque[self.index];
+        self.deque[self.index] = new;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.count < self.period {
+            self.count += 1;
+            self.weighted_sum += self.count as f64 * new;
+            self.sum += new;
+        } else {
+            // every value already in the window loses one unit of weight
+            self.weighted_sum += self.period as f64 * new - self.sum;
+            self.sum = self.sum - old_val + new;
+        }
+        let count = self.count as f64;
+        self.weighted_sum / (count * (count + 1.0) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::WMA, Indicator};
+
+    #[test]
+    fn test_build() {
+        WMA::factory().with_window_size(5).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wma = WMA::factory().with_window_size(5).build().unwrap();
+        assert_eq!(wma.next(10.0), 10.0);
+        assert_eq!(wma.next(20.0), 16.666666666666668);
+        assert_eq!(wma.next(15.0), 15.833333333333334);
+        assert_eq!(wma.next(10.0), 13.5);
+        assert_eq!(wma.next(10.0), 12.333333333333334);
+        assert_eq!(wma.next(10.0), 11.333333333333334);
+        assert_eq!(wma.next(12.5), 11.166666666666666);
+    }
+}
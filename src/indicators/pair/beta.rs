@@ -0,0 +1,170 @@
+use crate::{
+    indicators::{pair::co_moments::CoMoments, ReturnKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct BetaFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+}
+
+/// least squares fit of the asset against the benchmark: `asset = alpha + beta * benchmark`
+#[derive(Clone, PartialEq, Debug)]
+pub struct BetaResult {
+    pub beta: f64,
+    pub alpha: f64,
+}
+
+/// Rolling beta and alpha of an asset against a benchmark, with inputs given as
+/// `(asset, benchmark)`. Fitted on the returns when configured with `with_returns`, which is the
+/// usual definition of beta; on raw prices `beta` is the hedge ratio. `beta` is zero while the
+/// benchmark has no spread
+#[derive(Clone, Debug)]
+pub struct Beta {
+    moments: CoMoments,
+}
+
+impl BetaFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn build(self) -> Result<Beta, Error> {
+        check_window_size(self.window_size)?;
+        Ok(Beta {
+            moments: CoMoments::new(self.window_size, self.return_kind),
+        })
+    }
+}
+
+impl<A: Close, B: Close> Indicator<(A, B)> for Beta {
+    type Output = BetaResult;
+    fn next(&mut self, next: (A, B)) -> Self::Output {
+        if !self.moments.push(next.0.close(), next.1.close()) {
+            return BetaResult {
+                beta: 0.0,
+                alpha: 0.0,
+            };
+        }
+        let (_, m2_benchmark) = self.moments.squared_deviations();
+        let beta = if self.moments.flat().1 {
+            0.0
+        } else {
+            self.moments.co_moment() / m2_benchmark
+        };
+        let (mean_asset, mean_benchmark) = self.moments.means();
+        BetaResult {
+            beta,
+            alpha: mean_asset - beta * mean_benchmark,
+        }
+    }
+}
+
+impl<A: Close, B: Close> indicators::Pair<A, B> for Beta {}
+
+impl Beta {
+    pub fn factory() -> BetaFactory {
+        BetaFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Beta, indicators::BetaResult, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Beta::factory()
+            .with_window_size(20)
+            .with_returns(ReturnKind::Simple)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut beta = Beta::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(
+            beta.next((10.0, 5.0)),
+            BetaResult {
+                beta: 0.0,
+                alpha: 10.0,
+            }
+        );
+        assert_eq!(
+            beta.next((20.0, 9.0)),
+            BetaResult {
+                beta: 2.5,
+                alpha: -2.5,
+            }
+        );
+        assert_eq!(
+            beta.next((15.0, 8.0)),
+            BetaResult {
+                beta: 2.3076923076923075,
+                alpha: -1.9230769230769198,
+            }
+        );
+        assert_eq!(
+            beta.next((10.0, 6.0)),
+            BetaResult {
+                beta: 2.5,
+                alpha: -3.75,
+            }
+        );
+        assert_eq!(
+            beta.next((10.0, 5.0)),
+            BetaResult {
+                beta: 2.3484848484848486,
+                alpha: -2.5,
+            }
+        );
+        assert_eq!(
+            beta.next((10.0, 5.0)),
+            BetaResult {
+                beta: 2.3484848484848486,
+                alpha: -2.5,
+            }
+        );
+        assert_eq!(
+            beta.next((12.5, 6.0)),
+            BetaResult {
+                beta: 1.6666666666666665,
+                alpha: 1.5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_returns() {
+        let mut beta = Beta::factory()
+            .with_window_size(3)
+            .with_returns(ReturnKind::Simple)
+            .build()
+            .unwrap();
+
+        // the asset moves twice as much as the benchmark
+        beta.next((100.0, 100.0));
+        beta.next((120.0, 110.0));
+        beta.next((96.0, 99.0));
+        let result = beta.next((115.2, 108.9));
+        assert!((result.beta - 2.0).abs() < 1e-12);
+        assert!(result.alpha.abs() < 1e-12);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use crate::indicators::{ReturnKind, VarianceKind};
+
+/// Means, variances and covariance of two series over a rolling window, updated Welford style
+/// as pairs enter and leave the window. The sums are rebuilt from the window every time it turns
+/// over, so rounding error can't build up
+#[derive(Clone, Debug)]
+pub(crate) struct CoMoments {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+    prev: Option<(f64, f64)>,
+    window: VecDeque<(f64, f64)>,
+    updates: usize,
+    mean_a: f64,
+    mean_b: f64,
+    m2_a: f64,
+    m2_b: f64,
+    co_moment: f64,
+    // the largest squared deviations since the sums were last rebuilt, which bound the rounding
+    // error the updates have left in them
+    peak_a: f64,
+    peak_b: f64,
+}
+
+impl CoMoments {
+    pub(crate) fn new(window_size: usize, return_kind: Option<ReturnKind>) -> Self {
+        Self {
+            window_size,
+            return_kind,
+            prev: None,
+            window: VecDeque::with_capacity(window_size),
+            updates: 0,
+            mean_a: 0.0,
+            mean_b: 0.0,
+            m2_a: 0.0,
+            m2_b: 0.0,
+            co_moment: 0.0,
+            peak_a: 0.0,
+            peak_b: 0.0,
+        }
+    }
+
+    /// add a pair to the window, converting both values to returns first if configured. Returns
+    /// `false` when there is no pair to add yet
+    pub(crate) fn push(&mut self, a: f64, b: f64) -> bool {
+        let (a, b) = match &self.return_kind {
+            None => (a, b),
+            Some(kind) => match self.prev.replace((a, b)) {
+                Some((prev_a, prev_b)) => (kind.between(prev_a, a), kind.between(prev_b, b)),
+                None => return false,
+            },
+        };
+
+        if self.window.len() == self.window_size {
+            let (old_a, old_b) = self.window.pop_front().unwrap();
+            self.remove(old_a, old_b);
+        }
+        self.window.push_back((a, b));
+        self.add(a, b);
+
+        self.updates += 1;
+        if self.updates >= self.window_size {
+            self.recompute();
+        }
+        true
+    }
+
+    fn add(&mut self, a: f64, b: f64) {
+        let n = self.window.len() as f64;
+        let delta_a = a - self.mean_a;
+        let delta_b = b - self.mean_b;
+        self.mean_a += delta_a / n;
+        self.mean_b += delta_b / n;
+        self.m2_a += delta_a * (a - self.mean_a);
+        self.m2_b += delta_b * (b - self.mean_b);
+        self.co_moment += delta_a * (b - self.mean_b);
+        self.peak_a = self.peak_a.max(self.m2_a);
+        self.peak_b = self.peak_b.max(self.m2_b);
+    }
+
+    // the inverse of `add`, called after the pair has left the window
+    fn remove(&mut self, a: f64, b: f64) {
+        let n = self.window.len() as f64;
+        if n == 0.0 {
+            self.mean_a = 0.0;
+            self.mean_b = 0.0;
+            self.m2_a = 0.0;
+            self.m2_b = 0.0;
+            self.co_moment = 0.0;
+            return;
+        }
+        let mean_a = self.mean_a - (a - self.mean_a) / n;
+        let mean_b = self.mean_b - (b - self.mean_b) / n;
+        self.m2_a -= (a - mean_a) * (a - self.mean_a);
+        self.m2_b -= (b - mean_b) * (b - self.mean_b);
+        self.co_moment -= (a - mean_a) * (b - self.mean_b);
+        self.mean_a = mean_a;
+        self.mean_b = mean_b;
+    }
+
+    fn recompute(&mut self) {
+        self.updates = 0;
+        let n = self.window.len() as f64;
+        self.mean_a = self.window.iter().map(|(a, _)| a).sum::<f64>() / n;
+        self.mean_b = self.window.iter().map(|(_, b)| b).sum::<f64>() / n;
+        let (mut m2_a, mut m2_b, mut co_moment) = (0.0, 0.0, 0.0);
+        for (a, b) in self.window.iter() {
+            m2_a += (a - self.mean_a).powi(2);
+            m2_b += (b - self.mean_b).powi(2);
+            co_moment += (a - self.mean_a) * (b - self.mean_b);
+        }
+        self.m2_a = m2_a;
+        self.m2_b = m2_b;
+        self.co_moment = co_moment;
+        self.peak_a = m2_a;
+        self.peak_b = m2_b;
+    }
+
+    pub(crate) fn means(&self) -> (f64, f64) {
+        (self.mean_a, self.mean_b)
+    }
+
+    fn divisor(&self, kind: &VarianceKind) -> f64 {
+        match kind {
+            VarianceKind::Population => self.window.len() as f64,
+            VarianceKind::Sample => self.window.len() as f64 - 1.0,
+        }
+    }
+
+    pub(crate) fn covariance(&self, kind: &VarianceKind) -> f64 {
+        let divisor = self.divisor(kind);
+        if divisor <= 0.0 {
+            return 0.0;
+        }
+        self.co_moment / divisor
+    }
+
+    /// the sums of squared deviations of each series, which share the covariance's divisor
+    pub(crate) fn squared_deviations(&self) -> (f64, f64) {
+        (self.m2_a.max(0.0), self.m2_b.max(0.0))
+    }
+
+    /// whether each series is constant over the window. The windowed updates rarely leave
+    /// exactly zero squared deviations, so anything within their rounding error of the window's
+    /// mean squared and of the deviations seen since the last rebuild counts as zero
+    pub(crate) fn flat(&self) -> (bool, bool) {
+        let n = self.window.len() as f64;
+        let tolerance = |mean: f64, peak: f64| 4.0 * f64::EPSILON * (n * mean * mean + peak);
+        (
+            self.m2_a <= tolerance(self.mean_a, self.peak_a),
+            self.m2_b <= tolerance(self.mean_b, self.peak_b),
+        )
+    }
+
+    pub(crate) fn co_moment(&self) -> f64 {
+        self.co_moment
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    indicators::{pair::co_moments::CoMoments, ReturnKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct CorrelationFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+}
+
+/// Rolling Pearson correlation between two series, or between their returns when configured with
+/// `with_returns`. Returns zero while either series has no spread
+#[derive(Clone, Debug)]
+pub struct Correlation {
+    moments: CoMoments,
+}
+
+impl CorrelationFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn build(self) -> Result<Correlation, Error> {
+        check_window_size(self.window_size)?;
+        Ok(Correlation {
+            moments: CoMoments::new(self.window_size, self.return_kind),
+        })
+    }
+}
+
+impl<A: Close, B: Close> Indicator<(A, B)> for Correlation {
+    type Output = f64;
+    fn next(&mut self, next: (A, B)) -> Self::Output {
+        if !self.moments.push(next.0.close(), next.1.close()) {
+            return 0.0;
+        }
+        let (flat_a, flat_b) = self.moments.flat();
+        if flat_a || flat_b {
+            return 0.0;
+        }
+        let (m2_a, m2_b) = self.moments.squared_deviations();
+        (self.moments.co_moment() / (m2_a * m2_b).sqrt()).clamp(-1.0, 1.0)
+    }
+}
+
+impl<A: Close, B: Close> indicators::Pair<A, B> for Correlation {}
+
+impl Correlation {
+    pub fn factory() -> CorrelationFactory {
+        CorrelationFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Correlation, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Correlation::factory()
+            .with_window_size(20)
+            .with_returns(ReturnKind::Log)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut correlation = Correlation::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(correlation.next((10.0, 5.0)), 0.0);
+        assert_eq!(correlation.next((20.0, 9.0)), 1.0);
+        assert_eq!(correlation.next((15.0, 8.0)), 0.9607689228305227);
+        assert_eq!(correlation.next((10.0, 6.0)), 0.9534625892455922);
+        assert_eq!(correlation.next((10.0, 5.0)), 0.9539590550898286);
+        assert_eq!(correlation.next((10.0, 5.0)), 0.9539590550898286);
+        assert_eq!(correlation.next((12.5, 6.0)), 0.9128709291752767);
+    }
+    #[test]
+    fn test_flat() {
+        let mut correlation = Correlation::factory().with_window_size(7).build().unwrap();
+
+        for i in 0..40 {
+            // varied prices, then a window of the same price
+            let a = if i < 20 {
+                50_000.0 + ((i * 7919) % 13) as f64 * 1.37
+            } else {
+                50_000.1
+            };
+            let b = 100.0 + ((i * 104_729) % 11) as f64 * 0.3;
+            let result = correlation.next((a, b));
+            if i >= 26 {
+                assert_eq!(result, 0.0);
+            }
+        }
+    }
+}
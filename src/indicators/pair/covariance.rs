@@ -0,0 +1,98 @@
+use crate::{
+    indicators::{pair::co_moments::CoMoments, ReturnKind, VarianceKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct CovarianceFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+    variance_kind: VarianceKind,
+}
+
+/// Rolling covariance between two series, or between their returns when configured with
+/// `with_returns`
+#[derive(Clone, Debug)]
+pub struct Covariance {
+    moments: CoMoments,
+    variance_kind: VarianceKind,
+}
+
+impl CovarianceFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+            variance_kind: VarianceKind::Population,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn with_variance_kind(mut self, variance_kind: VarianceKind) -> Self {
+        self.variance_kind = variance_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<Covariance, Error> {
+        check_window_size(self.window_size)?;
+        Ok(Covariance {
+            moments: CoMoments::new(self.window_size, self.return_kind),
+            variance_kind: self.variance_kind,
+        })
+    }
+}
+
+impl<A: Close, B: Close> Indicator<(A, B)> for Covariance {
+    type Output = f64;
+    fn next(&mut self, next: (A, B)) -> Self::Output {
+        if !self.moments.push(next.0.close(), next.1.close()) {
+            return 0.0;
+        }
+        self.moments.covariance(&self.variance_kind)
+    }
+}
+
+impl<A: Close, B: Close> indicators::Pair<A, B> for Covariance {}
+
+impl Covariance {
+    pub fn factory() -> CovarianceFactory {
+        CovarianceFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Covariance, indicators::VarianceKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Covariance::factory()
+            .with_window_size(20)
+            .with_variance_kind(VarianceKind::Sample)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut covariance = Covariance::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(covariance.next((10.0, 5.0)), 0.0);
+        assert_eq!(covariance.next((20.0, 9.0)), 10.0);
+        assert_eq!(covariance.next((15.0, 8.0)), 6.666666666666667);
+        assert_eq!(covariance.next((10.0, 6.0)), 6.25);
+        assert_eq!(covariance.next((10.0, 5.0)), 6.2);
+        assert_eq!(covariance.next((10.0, 5.0)), 6.2);
+        assert_eq!(covariance.next((12.5, 6.0)), 1.9999999999999993);
+    }
+}
@@ -0,0 +1,17 @@
+mod beta;
+pub use beta::{Beta, BetaResult};
+
+pub(crate) mod co_moments;
+
+mod correlation;
+pub use correlation::Correlation;
+
+mod covariance;
+pub use covariance::Covariance;
+
+#[allow(clippy::module_inception)]
+mod pair;
+pub use pair::Pair;
+
+mod spread;
+pub use spread::{Spread, SpreadKind, SpreadResult};
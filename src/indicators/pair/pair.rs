@@ -0,0 +1,2 @@
+/// trait `Pair<A, B>` marks indicators over two series, which take `(A, B)` tuples as input
+pub trait Pair<A, B>: crate::Indicator<(A, B)> {}
@@ -0,0 +1,131 @@
+use crate::*;
+use anyhow::Error;
+
+/// how the two series are combined
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpreadKind {
+    /// `a / b`
+    Ratio,
+    /// `a - b`
+    Difference,
+}
+
+pub struct SpreadFactory {
+    window_size: usize,
+    kind: SpreadKind,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpreadResult {
+    pub spread: f64,
+    pub z_score: f64,
+}
+
+/// Ratio or difference of two series, with its `ZScore` over the window
+#[derive(Clone, Debug)]
+pub struct Spread {
+    kind: SpreadKind,
+    z_score: indicators::ZScore,
+}
+
+impl SpreadFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            kind: SpreadKind::Ratio,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_kind(mut self, kind: SpreadKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn build(self) -> Result<Spread, Error> {
+        Ok(Spread {
+            kind: self.kind,
+            z_score: indicators::ZScore::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<A: Close, B: Close> Indicator<(A, B)> for Spread {
+    type Output = SpreadResult;
+    fn next(&mut self, next: (A, B)) -> Self::Output {
+        let (a, b) = (next.0.close(), next.1.close());
+        let spread = match self.kind {
+            SpreadKind::Ratio => a / b,
+            SpreadKind::Difference => a - b,
+        };
+        SpreadResult {
+            spread,
+            z_score: self.z_score.next(spread),
+        }
+    }
+}
+
+impl<A: Close, B: Close> indicators::Pair<A, B> for Spread {}
+
+impl Spread {
+    pub fn factory() -> SpreadFactory {
+        SpreadFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{Spread, SpreadKind, SpreadResult},
+        Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        Spread::factory()
+            .with_window_size(20)
+            .with_kind(SpreadKind::Difference)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut spread = Spread::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(
+            spread.next((10.0, 5.0)),
+            SpreadResult {
+                spread: 2.0,
+                z_score: 0.0,
+            }
+        );
+        assert_eq!(
+            spread.next((20.0, 8.0)),
+            SpreadResult {
+                spread: 2.5,
+                z_score: 1.0,
+            }
+        );
+        assert_eq!(
+            spread.next((15.0, 5.0)),
+            SpreadResult {
+                spread: 3.0,
+                z_score: 1.224744871391589,
+            }
+        );
+        assert_eq!(
+            spread.next((10.0, 5.0)),
+            SpreadResult {
+                spread: 2.0,
+                z_score: -1.224744871391589,
+            }
+        );
+    }
+}
@@ -0,0 +1,493 @@
+use std::collections::VecDeque;
+
+use anyhow::{anyhow, Error};
+
+use crate::{helpers::check_window_size, *};
+
+/// A candlestick pattern, reported on the bar that completes it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CandlePattern {
+    Doji,
+    Hammer,
+    HangingMan,
+    BullishMarubozu,
+    BearishMarubozu,
+    BullishEngulfing,
+    BearishEngulfing,
+    BullishHarami,
+    BearishHarami,
+    Piercing,
+    DarkCloudCover,
+    MorningStar,
+    EveningStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bar {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+impl Bar {
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn body_top(&self) -> f64 {
+        self.open.max(self.close)
+    }
+
+    fn body_bottom(&self) -> f64 {
+        self.open.min(self.close)
+    }
+
+    fn midpoint(&self) -> f64 {
+        (self.open + self.close) / 2.0
+    }
+
+    fn upper_shadow(&self) -> f64 {
+        self.high - self.body_top()
+    }
+
+    fn lower_shadow(&self) -> f64 {
+        self.body_bottom() - self.low
+    }
+
+    fn bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn bearish(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+pub struct CandlePatternsFactory {
+    doji_body: f64,
+    small_body: f64,
+    long_body: f64,
+    short_shadow: f64,
+    long_shadow: f64,
+    trend_window: usize,
+}
+
+/// Recognises classic candlestick patterns, returning every pattern completed by each bar.
+/// Bodies and shadows are measured against the range of their bar. The hammer and the hanging
+/// man have the same shape, and are told apart by the trend of the closes over the
+/// `trend_window` bars before them, so neither is reported until that many bars have been seen.
+/// The other patterns are recognised by shape alone
+#[derive(Clone, Debug)]
+pub struct CandlePatterns {
+    doji_body: f64,
+    small_body: f64,
+    long_body: f64,
+    short_shadow: f64,
+    long_shadow: f64,
+    trend_window: usize,
+    history: VecDeque<Bar>,
+}
+
+impl CandlePatternsFactory {
+    pub fn new() -> Self {
+        Self {
+            doji_body: 0.1,
+            small_body: 0.3,
+            long_body: 0.6,
+            short_shadow: 0.1,
+            long_shadow: 2.0,
+            trend_window: 5,
+        }
+    }
+
+    /// the largest body, as a fraction of the range, of a doji
+    pub fn with_doji_body(mut self, doji_body: f64) -> Self {
+        self.doji_body = doji_body;
+        self
+    }
+
+    /// the largest body, as a fraction of the range, of a hammer, hanging man or the middle bar
+    /// of a star
+    pub fn with_small_body(mut self, small_body: f64) -> Self {
+        self.small_body = small_body;
+        self
+    }
+
+    /// the smallest body, as a fraction of the range, of the bars that set up or confirm a
+    /// multi-bar pattern
+    pub fn with_long_body(mut self, long_body: f64) -> Self {
+        self.long_body = long_body;
+        self
+    }
+
+    /// the largest shadow, as a fraction of the range, of a marubozu, or above a hammer or
+    /// hanging man
+    pub fn with_short_shadow(mut self, short_shadow: f64) -> Self {
+        self.short_shadow = short_shadow;
+        self
+    }
+
+    /// the smallest lower shadow of a hammer or hanging man, as a multiple of its body
+    pub fn with_long_shadow(mut self, long_shadow: f64) -> Self {
+        self.long_shadow = long_shadow;
+        self
+    }
+
+    /// the number of closes before a bar whose trend separates a hammer from a hanging man
+    pub fn with_trend_window(mut self, trend_window: usize) -> Self {
+        self.trend_window = trend_window;
+        self
+    }
+
+    pub fn build(self) -> Result<CandlePatterns, Error> {
+        for (name, fraction) in [
+            ("Doji body", self.doji_body),
+            ("Small body", self.small_body),
+            ("Long body", self.long_body),
+            ("Short shadow", self.short_shadow),
+        ] {
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                return Err(anyhow!(
+                    "{} must be greater than zero and at most one. You used {}",
+                    name,
+                    fraction
+                ));
+            }
+        }
+        if self.long_shadow <= 0.0 || !self.long_shadow.is_finite() {
+            return Err(anyhow!(
+                "Long shadow must be greater than zero and a real number. You used {}",
+                self.long_shadow
+            ));
+        }
+        check_window_size(self.trend_window)?;
+        Ok(CandlePatterns {
+            doji_body: self.doji_body,
+            small_body: self.small_body,
+            long_body: self.long_body,
+            short_shadow: self.short_shadow,
+            long_shadow: self.long_shadow,
+            trend_window: self.trend_window,
+            history: VecDeque::with_capacity(self.trend_window.max(2)),
+        })
+    }
+}
+
+impl<T: Open + High + Low + Close> Indicator<T> for CandlePatterns {
+    type Output = Vec<CandlePattern>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let bar = Bar {
+            open: next.open(),
+            high: next.high(),
+            low: next.low(),
+            close: next.close(),
+        };
+        let mut patterns = Vec::new();
+        self.single(bar, &mut patterns);
+        if let Some(&previous) = self.history.back() {
+            self.double(previous, bar, &mut patterns);
+        }
+        if self.history.len() >= 2 {
+            let first = self.history[self.history.len() - 2];
+            let second = self.history[self.history.len() - 1];
+            self.triple(first, second, bar, &mut patterns);
+        }
+
+        if self.history.len() == self.trend_window.max(2) {
+            self.history.pop_front();
+        }
+        self.history.push_back(bar);
+        patterns
+    }
+}
+
+impl<T: Open + High + Low + Close> indicators::Pattern<T> for CandlePatterns {}
+
+impl CandlePatterns {
+    pub fn factory() -> CandlePatternsFactory {
+        CandlePatternsFactory::new()
+    }
+
+    fn long(&self, bar: Bar) -> bool {
+        bar.body() >= self.long_body * bar.range() && bar.body() > 0.0
+    }
+
+    fn single(&self, bar: Bar, patterns: &mut Vec<CandlePattern>) {
+        let range = bar.range();
+        if bar.body() <= self.doji_body * range {
+            patterns.push(CandlePattern::Doji);
+        }
+
+        let hammer = range > 0.0
+            && bar.body() <= self.small_body * range
+            && bar.lower_shadow() >= self.long_shadow * bar.body()
+            && bar.upper_shadow() <= self.short_shadow * range;
+        if hammer && self.history.len() >= self.trend_window {
+            let first = self.history[self.history.len() - self.trend_window].close;
+            let last = self.history[self.history.len() - 1].close;
+            if last < first {
+                patterns.push(CandlePattern::Hammer);
+            } else if last > first {
+                patterns.push(CandlePattern::HangingMan);
+            }
+        }
+
+        if bar.body() > 0.0
+            && bar.upper_shadow() <= self.short_shadow * range
+            && bar.lower_shadow() <= self.short_shadow * range
+        {
+            patterns.push(if bar.bullish() {
+                CandlePattern::BullishMarubozu
+            } else {
+                CandlePattern::BearishMarubozu
+            });
+        }
+    }
+
+    fn double(&self, previous: Bar, bar: Bar, patterns: &mut Vec<CandlePattern>) {
+        if bar.body() > previous.body()
+            && bar.body_top() >= previous.body_top()
+            && bar.body_bottom() <= previous.body_bottom()
+        {
+            if previous.bearish() && bar.bullish() {
+                patterns.push(CandlePattern::BullishEngulfing);
+            } else if previous.bullish() && bar.bearish() {
+                patterns.push(CandlePattern::BearishEngulfing);
+            }
+        }
+
+        if self.long(previous)
+            && bar.body() < previous.body()
+            && bar.body_top() <= previous.body_top()
+            && bar.body_bottom() >= previous.body_bottom()
+        {
+            if previous.bearish() && bar.bullish() {
+                patterns.push(CandlePattern::BullishHarami);
+            } else if previous.bullish() && bar.bearish() {
+                patterns.push(CandlePattern::BearishHarami);
+            }
+        }
+
+        // the second bar gaps beyond the first and closes past the middle of its body
+        if self.long(previous)
+            && previous.bearish()
+            && bar.bullish()
+            && bar.open < previous.low
+            && bar.close > previous.midpoint()
+            && bar.close < previous.open
+        {
+            patterns.push(CandlePattern::Piercing);
+        }
+        if self.long(previous)
+            && previous.bullish()
+            && bar.bearish()
+            && bar.open > previous.high
+            && bar.close < previous.midpoint()
+            && bar.close > previous.open
+        {
+            patterns.push(CandlePattern::DarkCloudCover);
+        }
+    }
+
+    fn triple(&self, first: Bar, second: Bar, bar: Bar, patterns: &mut Vec<CandlePattern>) {
+        let star = second.body() <= self.small_body * second.range();
+        if star
+            && self.long(first)
+            && first.bearish()
+            && second.body_top() < first.close
+            && bar.bullish()
+            && bar.close > first.midpoint()
+        {
+            patterns.push(CandlePattern::MorningStar);
+        }
+        if star
+            && self.long(first)
+            && first.bullish()
+            && second.body_bottom() > first.close
+            && bar.bearish()
+            && bar.close < first.midpoint()
+        {
+            patterns.push(CandlePattern::EveningStar);
+        }
+
+        // each bar opens within the body of the one before and closes further on
+        let bars = [first, second, bar];
+        if bars.iter().all(|bar| self.long(*bar)) {
+            let steps = || bars.windows(2).map(|pair| (pair[0], pair[1]));
+            if bars.iter().all(Bar::bullish)
+                && steps().all(|(a, b)| b.open >= a.open && b.open <= a.close && b.close > a.close)
+            {
+                patterns.push(CandlePattern::ThreeWhiteSoldiers);
+            }
+            if bars.iter().all(Bar::bearish)
+                && steps().all(|(a, b)| b.open <= a.open && b.open >= a.close && b.close < a.close)
+            {
+                patterns.push(CandlePattern::ThreeBlackCrows);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{CandlePattern::*, CandlePatterns},
+        Candle, Indicator,
+    };
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle::new(open, close, high, low, 0.0).unwrap()
+    }
+
+    fn patterns(bars: &[Candle]) -> Vec<Vec<crate::indicators::CandlePattern>> {
+        let mut candle_patterns = CandlePatterns::factory()
+            .with_trend_window(3)
+            .build()
+            .unwrap();
+        bars.iter()
+            .map(|bar| candle_patterns.next(bar.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build() {
+        CandlePatterns::factory()
+            .with_doji_body(0.05)
+            .with_small_body(0.25)
+            .with_long_body(0.7)
+            .with_short_shadow(0.05)
+            .with_long_shadow(3.0)
+            .with_trend_window(10)
+            .build()
+            .unwrap();
+        assert!(CandlePatterns::factory()
+            .with_doji_body(0.0)
+            .build()
+            .is_err());
+        assert!(CandlePatterns::factory()
+            .with_long_body(1.5)
+            .build()
+            .is_err());
+        assert!(CandlePatterns::factory()
+            .with_long_shadow(-1.0)
+            .build()
+            .is_err());
+        assert!(CandlePatterns::factory()
+            .with_trend_window(1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_single() {
+        assert_eq!(
+            patterns(&[
+                bar(10.0, 11.0, 9.0, 10.1),
+                bar(10.0, 12.0, 10.0, 12.0),
+                bar(12.0, 12.0, 10.0, 10.0),
+            ]),
+            [vec![Doji], vec![BullishMarubozu], vec![BearishMarubozu]]
+        );
+    }
+
+    #[test]
+    fn test_hammer() {
+        let hammer = bar(9.0, 9.6, 6.0, 9.5);
+        assert_eq!(
+            patterns(&[
+                bar(12.0, 12.5, 10.5, 11.0),
+                bar(11.0, 11.5, 9.5, 10.0),
+                bar(10.0, 10.5, 8.5, 9.0),
+                hammer.clone(),
+            ])[3],
+            [Hammer]
+        );
+        assert_eq!(
+            patterns(&[
+                bar(7.0, 8.5, 6.5, 8.0),
+                bar(8.0, 9.5, 7.5, 9.0),
+                bar(9.0, 10.5, 8.5, 10.0),
+                hammer.clone(),
+            ])[3],
+            [HangingMan]
+        );
+        // the trend is unknown until enough bars have been seen
+        assert_eq!(patterns(&[bar(10.0, 10.5, 8.5, 9.0), hammer])[1], []);
+    }
+
+    #[test]
+    fn test_double() {
+        assert_eq!(
+            patterns(&[bar(10.0, 10.5, 8.5, 9.0), bar(8.8, 10.8, 8.6, 10.5)])[1],
+            [BullishEngulfing]
+        );
+        assert_eq!(
+            patterns(&[bar(9.0, 10.5, 8.5, 10.0), bar(10.2, 10.4, 8.4, 8.6)])[1],
+            [BearishEngulfing]
+        );
+        assert_eq!(
+            patterns(&[bar(20.0, 21.5, 10.5, 12.0), bar(14.0, 17.0, 13.0, 16.0)])[1],
+            [BullishHarami]
+        );
+        assert_eq!(
+            patterns(&[bar(12.0, 21.5, 10.5, 20.0), bar(18.0, 19.0, 15.0, 16.0)])[1],
+            [BearishHarami]
+        );
+        assert_eq!(
+            patterns(&[bar(20.0, 21.5, 10.5, 12.0), bar(10.0, 19.5, 9.0, 18.0)])[1],
+            [Piercing]
+        );
+        assert_eq!(
+            patterns(&[bar(12.0, 21.5, 10.5, 20.0), bar(22.0, 23.0, 12.5, 14.0)])[1],
+            [DarkCloudCover]
+        );
+        // closing short of the middle of the body is not a piercing line
+        assert_eq!(
+            patterns(&[bar(20.0, 21.5, 10.5, 12.0), bar(10.0, 16.5, 9.5, 15.0)])[1],
+            []
+        );
+    }
+
+    #[test]
+    fn test_triple() {
+        assert_eq!(
+            patterns(&[
+                bar(20.0, 21.5, 10.5, 12.0),
+                bar(10.5, 11.5, 9.0, 10.0),
+                bar(11.0, 20.5, 10.5, 19.0),
+            ])[2],
+            [MorningStar]
+        );
+        assert_eq!(
+            patterns(&[
+                bar(12.0, 21.5, 10.5, 20.0),
+                bar(21.5, 23.0, 20.5, 22.0),
+                bar(21.0, 21.5, 11.5, 13.0),
+            ])[2],
+            [EveningStar]
+        );
+        assert_eq!(
+            patterns(&[
+                bar(10.0, 13.5, 9.5, 13.0),
+                bar(12.0, 15.5, 11.5, 15.0),
+                bar(14.0, 17.5, 13.5, 17.0),
+            ])[2],
+            [ThreeWhiteSoldiers]
+        );
+        assert_eq!(
+            patterns(&[
+                bar(17.0, 17.5, 13.5, 14.0),
+                bar(15.0, 15.5, 11.5, 12.0),
+                bar(13.0, 13.5, 9.5, 10.0),
+            ])[2],
+            [ThreeBlackCrows]
+        );
+    }
+}
@@ -0,0 +1,6 @@
+mod candle_patterns;
+pub use candle_patterns::{CandlePattern, CandlePatterns};
+
+#[allow(clippy::module_inception)]
+mod patterns;
+pub use patterns::Pattern;
@@ -0,0 +1,3 @@
+/// trait `Pattern<T>` marks indicators that recognise chart or candlestick patterns in a series
+/// of bars
+pub trait Pattern<T>: crate::Indicator<T> {}
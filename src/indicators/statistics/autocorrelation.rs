@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use crate::{
+    indicators::{pair::co_moments::CoMoments, ReturnKind},
+    *,
+};
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct AutocorrelationFactory {
+    window_size: usize,
+    lag: usize,
+    return_kind: ReturnKind,
+}
+
+/// Lag-k autocorrelation of returns: the Pearson correlation between the last `window_size`
+/// returns and the returns `lag` bars before each of them. Returns zero until a lagged pair is
+/// available, or while either side has no spread
+#[derive(Clone, Debug)]
+pub struct Autocorrelation {
+    lag: usize,
+    return_kind: ReturnKind,
+    prev_close: Option<f64>,
+    returns: VecDeque<f64>,
+    moments: CoMoments,
+}
+
+impl AutocorrelationFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 20,
+            lag: 1,
+            return_kind: ReturnKind::Log,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_lag(mut self, lag: usize) -> Self {
+        self.lag = lag;
+        self
+    }
+
+    pub fn with_return_kind(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = return_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<Autocorrelation, Error> {
+        check_window_size(self.window_size)?;
+        if self.lag == 0 {
+            return Err(anyhow!("Lag must be at least one. You used {}", self.lag));
+        }
+        Ok(Autocorrelation {
+            lag: self.lag,
+            return_kind: self.return_kind,
+            prev_close: None,
+            returns: VecDeque::with_capacity(self.lag + 1),
+            moments: CoMoments::new(self.window_size, None),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for Autocorrelation {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        let new = next.close();
+        let prev = match self.prev_close.replace(new) {
+            Some(prev) => prev,
+            None => return 0.0,
+        };
+        self.returns.push_back(self.return_kind.between(prev, new));
+        if self.returns.len() <= self.lag {
+            return 0.0;
+        }
+        let lagged = self.returns.pop_front().unwrap();
+        self.moments.push(*self.returns.back().unwrap(), lagged);
+
+        let (flat_a, flat_b) = self.moments.flat();
+        if flat_a || flat_b {
+            return 0.0;
+        }
+        let (m2_a, m2_b) = self.moments.squared_deviations();
+        (self.moments.co_moment() / (m2_a * m2_b).sqrt()).clamp(-1.0, 1.0)
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for Autocorrelation {}
+
+impl Autocorrelation {
+    pub fn factory() -> AutocorrelationFactory {
+        AutocorrelationFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Autocorrelation, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Autocorrelation::factory()
+            .with_window_size(50)
+            .with_lag(5)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+        assert!(Autocorrelation::factory().with_lag(0).build().is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut autocorrelation = Autocorrelation::factory()
+            .with_window_size(4)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+
+        assert_eq!(autocorrelation.next(10.0), 0.0);
+        assert_eq!(autocorrelation.next(20.0), 0.0);
+        assert_eq!(autocorrelation.next(15.0), 0.0);
+        assert_eq!(autocorrelation.next(10.0), 1.0);
+        assert_eq!(autocorrelation.next(10.0), -0.33051527153449817);
+        assert_eq!(autocorrelation.next(10.0), -0.33477595120002646);
+        assert_eq!(autocorrelation.next(12.5), 0.5770071189250237);
+        assert_eq!(autocorrelation.next(11.0), -0.24419185976773766);
+        assert_eq!(autocorrelation.next(13.0), -0.785236566606105);
+    }
+    #[test]
+    fn test_flat() {
+        let mut autocorrelation = Autocorrelation::factory()
+            .with_window_size(7)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+
+        for i in 0..40 {
+            // varied prices, then enough of the same price for a window of zero returns
+            let close = if i < 20 {
+                50_000.0 + ((i * 7919) % 13) as f64 * 1.37
+            } else {
+                50_000.1
+            };
+            let result = autocorrelation.next(close);
+            if i >= 28 {
+                assert_eq!(result, 0.0);
+            }
+        }
+    }
+}
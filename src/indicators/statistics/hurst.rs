@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+
+use crate::{indicators::ReturnKind, *};
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct HurstFactory {
+    window_size: usize,
+    min_chunk_size: usize,
+    return_kind: ReturnKind,
+}
+
+/// Hurst exponent of the returns over the window, by rescaled range analysis. The window is split
+/// into chunks of `window_size`, `window_size / 2`, `window_size / 4`, ... returns, down to
+/// `min_chunk_size`. The exponent is the slope of the log of the average rescaled range of the
+/// chunks against the log of the chunk size. Each update is O(n log n).
+///
+/// Returns `None` until the window is full, or when fewer than two chunk sizes have any spread
+#[derive(Clone, Debug)]
+pub struct Hurst {
+    window_size: usize,
+    min_chunk_size: usize,
+    return_kind: ReturnKind,
+    prev_close: Option<f64>,
+    returns: VecDeque<f64>,
+}
+
+impl HurstFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 100,
+            min_chunk_size: 8,
+            return_kind: ReturnKind::Log,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_min_chunk_size(mut self, min_chunk_size: usize) -> Self {
+        self.min_chunk_size = min_chunk_size;
+        self
+    }
+
+    pub fn with_return_kind(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = return_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<Hurst, Error> {
+        check_window_size(self.min_chunk_size)?;
+        if self.window_size < 2 * self.min_chunk_size {
+            return Err(anyhow!(
+                "Window size must be at least twice the minimum chunk size. You used {} and {}",
+                self.window_size,
+                self.min_chunk_size
+            ));
+        }
+        Ok(Hurst {
+            window_size: self.window_size,
+            min_chunk_size: self.min_chunk_size,
+            return_kind: self.return_kind,
+            prev_close: None,
+            returns: VecDeque::with_capacity(self.window_size),
+        })
+    }
+}
+
+// range of the cumulative deviations from the mean, over the standard deviation
+fn rescaled_range<'a>(chunk: impl Iterator<Item = &'a f64> + Clone, len: usize) -> Option<f64> {
+    let mean = chunk.clone().sum::<f64>() / len as f64;
+    let (mut cumulative, mut max, mut min, mut squares) = (0.0_f64, 0.0_f64, 0.0_f64, 0.0);
+    for x in chunk {
+        cumulative += x - mean;
+        max = max.max(cumulative);
+        min = min.min(cumulative);
+        squares += (x - mean).powi(2);
+    }
+    let deviation = (squares / len as f64).sqrt();
+    (deviation > 0.0).then(|| (max - min) / deviation)
+}
+
+impl<T: Close> Indicator<T> for Hurst {
+    type Output = Option<f64>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let new = next.close();
+        let prev = self.prev_close.replace(new)?;
+        if self.returns.len() == self.window_size {
+            self.returns.pop_front();
+        }
+        self.returns.push_back(self.return_kind.between(prev, new));
+        if self.returns.len() < self.window_size {
+            return None;
+        }
+
+        let mut points = Vec::new();
+        let mut chunk_size = self.window_size;
+        while chunk_size >= self.min_chunk_size {
+            // use the most recent whole chunks
+            let chunks = self.window_size / chunk_size;
+            let start = self.window_size - chunks * chunk_size;
+            let ranges: Vec<f64> = (0..chunks)
+                .filter_map(|i| {
+                    let from = start + i * chunk_size;
+                    rescaled_range(self.returns.range(from..from + chunk_size), chunk_size)
+                })
+                .collect();
+            if !ranges.is_empty() {
+                let average = ranges.iter().sum::<f64>() / ranges.len() as f64;
+                if average > 0.0 {
+                    points.push(((chunk_size as f64).ln(), average.ln()));
+                }
+            }
+            chunk_size /= 2;
+        }
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxy: f64 = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum();
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        Some(sxy / sxx)
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for Hurst {}
+
+impl Hurst {
+    pub fn factory() -> HurstFactory {
+        HurstFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Hurst, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Hurst::factory()
+            .with_window_size(256)
+            .with_min_chunk_size(16)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+        assert!(Hurst::factory()
+            .with_window_size(10)
+            .with_min_chunk_size(8)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hurst = Hurst::factory()
+            .with_window_size(16)
+            .with_min_chunk_size(4)
+            .build()
+            .unwrap();
+
+        let mut state: u64 = 42;
+        let mut close = 100.0;
+        let mut values = Vec::new();
+        for _ in 0..40 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            close *= 1.0 + ((state >> 33) % 200) as f64 / 10_000.0 - 0.01;
+            values.push(hurst.next(close));
+        }
+        assert!(values[..16].iter().all(|h| h.is_none()));
+        assert_eq!(values[16], Some(0.7564976086657212));
+        assert_eq!(values[39], Some(0.8949463202993971));
+
+        // flat prices have no spread
+        let mut hurst = Hurst::factory()
+            .with_window_size(16)
+            .with_min_chunk_size(4)
+            .build()
+            .unwrap();
+        for _ in 0..20 {
+            assert_eq!(hurst.next(100.0), None);
+        }
+    }
+}
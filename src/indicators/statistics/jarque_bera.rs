@@ -0,0 +1,96 @@
+use crate::{
+    indicators::{statistics::moments::Moments, ReturnKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct JarqueBeraFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+}
+
+/// Jarque–Bera normality statistic `n / 6 * (S² + K² / 4)` of the window, from its skewness `S`
+/// and excess kurtosis `K`. Uses the closes, or their returns when configured with
+/// `with_returns`
+#[derive(Clone, Debug)]
+pub struct JarqueBera {
+    moments: Moments,
+}
+
+impl JarqueBeraFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn build(self) -> Result<JarqueBera, Error> {
+        check_window_size(self.window_size)?;
+        Ok(JarqueBera {
+            moments: Moments::new(self.window_size, self.return_kind),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for JarqueBera {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        if !self.moments.push(next.close()) {
+            return 0.0;
+        }
+        let skewness = self.moments.skewness();
+        let kurtosis = self.moments.kurtosis();
+        self.moments.len() as f64 / 6.0 * (skewness * skewness + kurtosis * kurtosis / 4.0)
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for JarqueBera {}
+
+impl JarqueBera {
+    pub fn factory() -> JarqueBeraFactory {
+        JarqueBeraFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::JarqueBera, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        JarqueBera::factory()
+            .with_window_size(20)
+            .with_returns(ReturnKind::Log)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut jarque_bera = JarqueBera::factory()
+            .with_window_size(5)
+            .with_returns(ReturnKind::Simple)
+            .build()
+            .unwrap();
+
+        assert_eq!(jarque_bera.next(10.0), 0.0);
+        assert_eq!(jarque_bera.next(20.0), 0.0);
+        assert_eq!(jarque_bera.next(15.0), 0.3333333333333333);
+        assert_eq!(jarque_bera.next(10.0), 0.5243059132577869);
+        assert_eq!(jarque_bera.next(10.0), 0.7539295761919078);
+        assert_eq!(jarque_bera.next(10.0), 1.2303391504167662);
+        assert_eq!(jarque_bera.next(12.5), 0.33256177046199514);
+    }
+}
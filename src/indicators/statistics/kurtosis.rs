@@ -0,0 +1,89 @@
+use crate::{
+    indicators::{statistics::moments::Moments, ReturnKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct KurtosisFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+}
+
+/// Excess kurtosis of the closes in the window, or of their returns when configured with
+/// `with_returns`
+#[derive(Clone, Debug)]
+pub struct Kurtosis {
+    moments: Moments,
+}
+
+impl KurtosisFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn build(self) -> Result<Kurtosis, Error> {
+        check_window_size(self.window_size)?;
+        Ok(Kurtosis {
+            moments: Moments::new(self.window_size, self.return_kind),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for Kurtosis {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        if !self.moments.push(next.close()) {
+            return 0.0;
+        }
+        self.moments.kurtosis()
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for Kurtosis {}
+
+impl Kurtosis {
+    pub fn factory() -> KurtosisFactory {
+        KurtosisFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Kurtosis, indicators::ReturnKind, Indicator};
+
+    #[test]
+    fn test_build() {
+        Kurtosis::factory()
+            .with_window_size(20)
+            .with_returns(ReturnKind::Simple)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kurtosis = Kurtosis::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(kurtosis.next(10.0), 0.0);
+        assert_eq!(kurtosis.next(20.0), -2.0);
+        assert_eq!(kurtosis.next(15.0), -1.5000000000000002);
+        assert_eq!(kurtosis.next(10.0), -1.371900826446281);
+        assert_eq!(kurtosis.next(10.0), -0.921875);
+        assert_eq!(kurtosis.next(10.0), -0.921875);
+        assert_eq!(kurtosis.next(12.5), -0.921875);
+    }
+}
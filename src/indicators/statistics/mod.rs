@@ -0,0 +1,41 @@
+mod autocorrelation;
+pub use autocorrelation::Autocorrelation;
+
+mod hurst;
+pub use hurst::Hurst;
+
+mod jarque_bera;
+pub use jarque_bera::JarqueBera;
+
+mod kurtosis;
+pub use kurtosis::Kurtosis;
+
+mod moments;
+
+mod rolling_max;
+pub use rolling_max::RollingMax;
+
+mod rolling_median;
+pub use rolling_median::RollingMedian;
+
+mod rolling_min;
+pub use rolling_min::RollingMin;
+
+mod rolling_percentile;
+pub use rolling_percentile::RollingPercentile;
+
+mod rolling_sum;
+pub use rolling_sum::RollingSum;
+
+mod skewness;
+pub use skewness::Skewness;
+
+#[allow(clippy::module_inception)]
+mod statistics;
+pub use statistics::Statistic;
+
+mod variance_ratio;
+pub use variance_ratio::{VarianceRatio, VarianceRatioResult};
+
+mod z_score;
+pub use z_score::ZScore;
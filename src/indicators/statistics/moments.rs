@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use crate::indicators::ReturnKind;
+
+/// Central moments of a rolling window, kept as power sums of the values relative to a shift.
+/// The shift is moved to the window mean and the sums rebuilt every time the window turns over,
+/// so the sums stay small and rounding error can't build up
+#[derive(Clone, Debug)]
+pub(crate) struct Moments {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+    prev_close: Option<f64>,
+    window: VecDeque<f64>,
+    updates: usize,
+    shift: f64,
+    sums: [f64; 4],
+}
+
+fn add_powers(sums: &mut [f64; 4], sign: f64, x: f64) {
+    let mut power = sign;
+    for sum in sums.iter_mut() {
+        power *= x;
+        *sum += power;
+    }
+}
+
+impl Moments {
+    pub(crate) fn new(window_size: usize, return_kind: Option<ReturnKind>) -> Self {
+        Self {
+            window_size,
+            return_kind,
+            prev_close: None,
+            window: VecDeque::with_capacity(window_size),
+            updates: 0,
+            shift: 0.0,
+            sums: [0.0; 4],
+        }
+    }
+
+    /// add a close to the window, converting it to a return first if configured. Returns `false`
+    /// when there is no value to add yet
+    pub(crate) fn push(&mut self, close: f64) -> bool {
+        let value = match &self.return_kind {
+            None => close,
+            Some(kind) => match self.prev_close.replace(close) {
+                Some(prev) => kind.between(prev, close),
+                None => return false,
+            },
+        };
+
+        if self.window.is_empty() {
+            self.shift = value;
+        }
+        if self.window.len() == self.window_size {
+            let old = self.window.pop_front().unwrap() - self.shift;
+            self.add(-1.0, old);
+        }
+        self.window.push_back(value);
+        self.add(1.0, value - self.shift);
+
+        self.updates += 1;
+        if self.updates >= self.window_size {
+            self.recompute();
+        }
+        true
+    }
+
+    fn add(&mut self, sign: f64, x: f64) {
+        add_powers(&mut self.sums, sign, x);
+    }
+
+    fn recompute(&mut self) {
+        self.updates = 0;
+        self.shift = self.window.iter().sum::<f64>() / self.window.len() as f64;
+        self.sums = [0.0; 4];
+        for x in self.window.iter() {
+            add_powers(&mut self.sums, 1.0, x - self.shift);
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// population variance, third and fourth central moments
+    pub(crate) fn central(&self) -> (f64, f64, f64) {
+        let n = self.window.len() as f64;
+        let mean = self.sums[0] / n;
+        let (s2, s3, s4) = (self.sums[1] / n, self.sums[2] / n, self.sums[3] / n);
+        let m2 = (s2 - mean * mean).max(0.0);
+        let m3 = s3 - 3.0 * mean * s2 + 2.0 * mean.powi(3);
+        let m4 = s4 - 4.0 * mean * s3 + 6.0 * mean * mean * s2 - 3.0 * mean.powi(4);
+        (m2, m3, m4.max(0.0))
+    }
+
+    /// population skewness, zero while the window has no spread
+    pub(crate) fn skewness(&self) -> f64 {
+        let (m2, m3, _) = self.central();
+        if m2 == 0.0 {
+            return 0.0;
+        }
+        m3 / m2.powf(1.5)
+    }
+
+    /// excess kurtosis, zero while the window has no spread
+    pub(crate) fn kurtosis(&self) -> f64 {
+        let (m2, _, m4) = self.central();
+        if m2 == 0.0 {
+            return 0.0;
+        }
+        m4 / (m2 * m2) - 3.0
+    }
+}
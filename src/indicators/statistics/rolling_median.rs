@@ -0,0 +1,76 @@
+use crate::{indicators::RollingPercentile, *};
+use anyhow::Error;
+
+pub struct RollingMedianFactory {
+    window_size: usize,
+}
+
+/// Median of the closes in the window. Even sized windows average the two middle values
+#[derive(Clone, Debug)]
+pub struct RollingMedian {
+    percentile: RollingPercentile,
+}
+
+impl RollingMedianFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<RollingMedian, Error> {
+        Ok(RollingMedian {
+            percentile: RollingPercentile::factory()
+                .with_window_size(self.window_size)
+                .with_percentile(50.0)
+                .build()?,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for RollingMedian {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.percentile.next(next)
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for RollingMedian {}
+
+impl RollingMedian {
+    pub fn factory() -> RollingMedianFactory {
+        RollingMedianFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RollingMedian, Indicator};
+
+    #[test]
+    fn test_build() {
+        RollingMedian::factory()
+            .with_window_size(5)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut median = RollingMedian::factory()
+            .with_window_size(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(median.next(10.0), 10.0);
+        assert_eq!(median.next(20.0), 15.0);
+        assert_eq!(median.next(15.0), 15.0);
+        assert_eq!(median.next(10.0), 12.5);
+        assert_eq!(median.next(10.0), 10.0);
+        assert_eq!(median.next(10.0), 10.0);
+        assert_eq!(median.next(12.5), 10.0);
+    }
+}
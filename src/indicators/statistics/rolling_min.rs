@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use crate::*;
+use anyhow::Error;
+use helpers::*;
+
+pub struct RollingMinFactory {
+    window_size: usize,
+}
+
+/// Lowest close of the window, kept in a monotonic deque for O(1) amortised updates
+#[derive(Clone, Debug)]
+pub struct RollingMin {
+    window_size: usize,
+    index: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingMinFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<RollingMin, Error> {
+        check_window_size(self.window_size)?;
+        Ok(RollingMin {
+            window_size: self.window_size,
+            index: 0,
+            deque: VecDeque::with_capacity(self.window_size),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for RollingMin {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        let new = next.close();
+        while self.deque.back().is_some_and(|(_, value)| *value >= new) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.index, new));
+        if self.deque[0].0 + self.window_size <= self.index {
+            self.deque.pop_front();
+        }
+        self.index += 1;
+        self.deque[0].1
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for RollingMin {}
+
+impl RollingMin {
+    pub fn factory() -> RollingMinFactory {
+        RollingMinFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RollingMin, Indicator};
+
+    #[test]
+    fn test_build() {
+        RollingMin::factory().with_window_size(5).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut min = RollingMin::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(min.next(10.0), 10.0);
+        assert_eq!(min.next(20.0), 10.0);
+        assert_eq!(min.next(15.0), 10.0);
+        assert_eq!(min.next(17.0), 15.0);
+        assert_eq!(min.next(18.0), 15.0);
+        assert_eq!(min.next(19.0), 17.0);
+        assert_eq!(min.next(12.5), 12.5);
+    }
+}
@@ -0,0 +1,199 @@
+use std::{cmp::Ordering, collections::BTreeSet, collections::VecDeque};
+
+use crate::*;
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct RollingPercentileFactory {
+    window_size: usize,
+    percentile: f64,
+}
+
+// window values are tagged with their position so that equal values stay distinct in the sets
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    value: f64,
+    index: u64,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value
+            .total_cmp(&other.value)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// Percentile of the closes in the window, linearly interpolated between the two nearest ranks.
+/// The window is split into two ordered sets at the percentile's rank, so each update is
+/// O(log n)
+#[derive(Clone, Debug)]
+pub struct RollingPercentile {
+    window_size: usize,
+    fraction: f64,
+    index: u64,
+    window: VecDeque<Entry>,
+    lower: BTreeSet<Entry>,
+    upper: BTreeSet<Entry>,
+}
+
+impl RollingPercentileFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            percentile: 50.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// the percentile to track, from 0 to 100
+    pub fn with_percentile(mut self, percentile: f64) -> Self {
+        self.percentile = percentile;
+        self
+    }
+
+    pub fn build(self) -> Result<RollingPercentile, Error> {
+        check_window_size(self.window_size)?;
+        if !(0.0..=100.0).contains(&self.percentile) {
+            return Err(anyhow!(
+                "Percentile must be between 0 and 100. You used {}",
+                self.percentile
+            ));
+        }
+        Ok(RollingPercentile {
+            window_size: self.window_size,
+            fraction: self.percentile / 100.0,
+            index: 0,
+            window: VecDeque::with_capacity(self.window_size),
+            lower: BTreeSet::new(),
+            upper: BTreeSet::new(),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for RollingPercentile {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        let entry = Entry {
+            value: next.close(),
+            index: self.index,
+        };
+        self.index += 1;
+
+        if self.window.len() == self.window_size {
+            let old = self.window.pop_front().unwrap();
+            if !self.lower.remove(&old) {
+                self.upper.remove(&old);
+            }
+        }
+        self.window.push_back(entry);
+        match self.lower.last() {
+            Some(last) if entry < *last => self.lower.insert(entry),
+            _ => self.upper.insert(entry),
+        };
+
+        // the lower set holds every value up to and including the rank below the percentile
+        let rank = self.fraction * (self.window.len() - 1) as f64;
+        let lower_len = rank.floor() as usize + 1;
+        while self.lower.len() > lower_len {
+            let moved = self.lower.pop_last().unwrap();
+            self.upper.insert(moved);
+        }
+        while self.lower.len() < lower_len {
+            let moved = self.upper.pop_first().unwrap();
+            self.lower.insert(moved);
+        }
+
+        let below = self.lower.last().unwrap().value;
+        match self.upper.first() {
+            Some(above) if rank > rank.floor() => {
+                below + (rank - rank.floor()) * (above.value - below)
+            }
+            _ => below,
+        }
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for RollingPercentile {}
+
+impl RollingPercentile {
+    pub fn factory() -> RollingPercentileFactory {
+        RollingPercentileFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RollingPercentile, Indicator};
+
+    #[test]
+    fn test_build() {
+        RollingPercentile::factory()
+            .with_window_size(20)
+            .with_percentile(95.0)
+            .build()
+            .unwrap();
+        assert!(RollingPercentile::factory()
+            .with_percentile(101.0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut percentile = RollingPercentile::factory()
+            .with_window_size(5)
+            .with_percentile(90.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(percentile.next(10.0), 10.0);
+        assert_eq!(percentile.next(20.0), 19.0);
+        assert_eq!(percentile.next(15.0), 19.0);
+        assert_eq!(percentile.next(10.0), 18.5);
+        assert_eq!(percentile.next(10.0), 18.0);
+        assert_eq!(percentile.next(10.0), 18.0);
+        assert_eq!(percentile.next(12.5), 14.0);
+    }
+
+    #[test]
+    fn test_against_sorted_window() {
+        for percentile in [0.0, 25.0, 50.0, 62.5, 100.0] {
+            let mut rolling = RollingPercentile::factory()
+                .with_window_size(7)
+                .with_percentile(percentile)
+                .build()
+                .unwrap();
+
+            // plenty of repeated values
+            let values: Vec<f64> = (0..500).map(|i| ((i * 37) % 11) as f64).collect();
+            for (i, value) in values.iter().enumerate() {
+                let mut window = values[i.saturating_sub(6)..=i].to_vec();
+                window.sort_by(f64::total_cmp);
+                let rank = percentile / 100.0 * (window.len() - 1) as f64;
+                let below = window[rank.floor() as usize];
+                let above = window[rank.ceil() as usize];
+                let expected = below + (rank - rank.floor()) * (above - below);
+                assert_eq!(rolling.next(*value), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::*;
+use anyhow::Error;
+
+pub struct RollingSumFactory {
+    window_size: usize,
+}
+
+/// Sum of the closes in the window
+#[derive(Clone, Debug)]
+pub struct RollingSum {
+    average: indicators::SMA,
+}
+
+impl RollingSumFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<RollingSum, Error> {
+        Ok(RollingSum {
+            average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for RollingSum {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.average.next(next);
+        self.average.sum()
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for RollingSum {}
+
+impl RollingSum {
+    pub fn factory() -> RollingSumFactory {
+        RollingSumFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RollingSum, Indicator};
+
+    #[test]
+    fn test_build() {
+        RollingSum::factory().with_window_size(5).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sum = RollingSum::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(sum.next(10.0), 10.0);
+        assert_eq!(sum.next(20.0), 30.0);
+        assert_eq!(sum.next(15.0), 45.0);
+        assert_eq!(sum.next(10.0), 55.0);
+        assert_eq!(sum.next(10.0), 65.0);
+        assert_eq!(sum.next(10.0), 65.0);
+        assert_eq!(sum.next(12.5), 57.5);
+    }
+}
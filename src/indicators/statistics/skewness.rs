@@ -0,0 +1,109 @@
+use crate::{
+    indicators::{statistics::moments::Moments, ReturnKind},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct SkewnessFactory {
+    window_size: usize,
+    return_kind: Option<ReturnKind>,
+}
+
+/// Population skewness of the closes in the window, or of their returns when configured with
+/// `with_returns`
+#[derive(Clone, Debug)]
+pub struct Skewness {
+    moments: Moments,
+}
+
+impl SkewnessFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            return_kind: None,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_returns(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = Some(return_kind);
+        self
+    }
+
+    pub fn build(self) -> Result<Skewness, Error> {
+        check_window_size(self.window_size)?;
+        Ok(Skewness {
+            moments: Moments::new(self.window_size, self.return_kind),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for Skewness {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        if !self.moments.push(next.close()) {
+            return 0.0;
+        }
+        self.moments.skewness()
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for Skewness {}
+
+impl Skewness {
+    pub fn factory() -> SkewnessFactory {
+        SkewnessFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::ReturnKind, indicators::Skewness, Indicator};
+
+    #[test]
+    fn test_build() {
+        Skewness::factory()
+            .with_window_size(20)
+            .with_returns(ReturnKind::Log)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut skewness = Skewness::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(skewness.next(10.0), 0.0);
+        assert_eq!(skewness.next(20.0), 0.0);
+        assert_eq!(skewness.next(15.0), 0.0);
+        assert_eq!(skewness.next(10.0), 0.49338220021815865);
+        assert_eq!(skewness.next(10.0), 0.84375);
+        assert_eq!(skewness.next(10.0), 0.84375);
+        assert_eq!(skewness.next(12.5), 0.84375);
+    }
+
+    #[test]
+    fn test_large_offset() {
+        let mut skewness = Skewness::factory().with_window_size(20).build().unwrap();
+
+        let values: Vec<f64> = (0..10_000_usize)
+            .map(|i| 60_000.0 + ((i * i * 7919) % 101) as f64 * 1e-3)
+            .collect();
+        for (i, value) in values.iter().enumerate() {
+            let result = skewness.next(*value);
+            if i >= 19 {
+                let window = &values[i - 19..=i];
+                let mean = window.iter().sum::<f64>() / 20.0;
+                let m2 = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 20.0;
+                let m3 = window.iter().map(|x| (x - mean).powi(3)).sum::<f64>() / 20.0;
+                let expected = m3 / m2.powf(1.5);
+                assert!((result - expected).abs() < 1e-6);
+            }
+        }
+    }
+}
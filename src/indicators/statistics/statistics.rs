@@ -0,0 +1 @@
+pub trait Statistic<T>: crate::Indicator<T> {}
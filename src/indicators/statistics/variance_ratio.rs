@@ -0,0 +1,182 @@
+use std::collections::VecDeque;
+
+use crate::{indicators::ReturnKind, *};
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct VarianceRatioFactory {
+    window_size: usize,
+    period: usize,
+    return_kind: ReturnKind,
+}
+
+/// Lo–MacKinlay variance ratio of the returns in the window, with its z-statistic under the
+/// homoskedastic random walk hypothesis. Ratios above one point to trending, below one to mean
+/// reversion
+#[derive(Clone, PartialEq, Debug)]
+pub struct VarianceRatioResult {
+    pub ratio: f64,
+    pub z_score: f64,
+}
+
+/// Compares the variance of overlapping `period` bar returns with `period` times the variance of
+/// single bar returns, over the last `window_size` returns, using Lo and MacKinlay's bias
+/// corrected estimators. Each update is O(n).
+///
+/// Returns `None` until the window is full, or while the returns have no spread
+#[derive(Clone, Debug)]
+pub struct VarianceRatio {
+    window_size: usize,
+    period: usize,
+    return_kind: ReturnKind,
+    prev_close: Option<f64>,
+    returns: VecDeque<f64>,
+}
+
+impl VarianceRatioFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 100,
+            period: 2,
+            return_kind: ReturnKind::Log,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// the number of bars in each aggregated return, `q` in Lo and MacKinlay's paper
+    pub fn with_period(mut self, period: usize) -> Self {
+        self.period = period;
+        self
+    }
+
+    pub fn with_return_kind(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = return_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<VarianceRatio, Error> {
+        check_window_size(self.period)?;
+        if self.window_size <= self.period {
+            return Err(anyhow!(
+                "Window size must be greater than the period. You used {} and {}",
+                self.window_size,
+                self.period
+            ));
+        }
+        Ok(VarianceRatio {
+            window_size: self.window_size,
+            period: self.period,
+            return_kind: self.return_kind,
+            prev_close: None,
+            returns: VecDeque::with_capacity(self.window_size),
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for VarianceRatio {
+    type Output = Option<VarianceRatioResult>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let new = next.close();
+        let prev = self.prev_close.replace(new)?;
+        if self.returns.len() == self.window_size {
+            self.returns.pop_front();
+        }
+        self.returns.push_back(self.return_kind.between(prev, new));
+        if self.returns.len() < self.window_size {
+            return None;
+        }
+
+        let n = self.window_size as f64;
+        let q = self.period as f64;
+        let mean = self.returns.iter().sum::<f64>() / n;
+        let variance = self.returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        if variance <= 0.0 {
+            return None;
+        }
+
+        let mut period_sum: f64 = self.returns.iter().take(self.period).sum();
+        let mut period_squares = (period_sum - q * mean).powi(2);
+        for i in self.period..self.window_size {
+            period_sum += self.returns[i] - self.returns[i - self.period];
+            period_squares += (period_sum - q * mean).powi(2);
+        }
+        let m = q * (n - q + 1.0) * (1.0 - q / n);
+        let ratio = period_squares / m / variance;
+
+        Some(VarianceRatioResult {
+            ratio,
+            z_score: (ratio - 1.0) / (2.0 * (2.0 * q - 1.0) * (q - 1.0) / (3.0 * q * n)).sqrt(),
+        })
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for VarianceRatio {}
+
+impl VarianceRatio {
+    pub fn factory() -> VarianceRatioFactory {
+        VarianceRatioFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::ReturnKind,
+        indicators::{VarianceRatio, VarianceRatioResult},
+        Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        VarianceRatio::factory()
+            .with_window_size(250)
+            .with_period(5)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+        assert!(VarianceRatio::factory()
+            .with_window_size(4)
+            .with_period(4)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut variance_ratio = VarianceRatio::factory()
+            .with_window_size(6)
+            .with_period(2)
+            .with_return_kind(ReturnKind::Simple)
+            .build()
+            .unwrap();
+
+        for x in [10.0, 20.0, 15.0, 10.0, 10.0, 10.0] {
+            assert_eq!(variance_ratio.next(x), None);
+        }
+        assert_eq!(
+            variance_ratio.next(12.5),
+            Some(VarianceRatioResult {
+                ratio: 0.8301792828685258,
+                z_score: -0.4159741047256295
+            })
+        );
+        assert_eq!(
+            variance_ratio.next(11.0),
+            Some(VarianceRatioResult {
+                ratio: 1.6742721356396641,
+                z_score: 1.6516226800938651
+            })
+        );
+        assert_eq!(
+            variance_ratio.next(13.0),
+            Some(VarianceRatioResult {
+                ratio: 0.6656778699971361,
+                z_score: -0.8189186282274394
+            })
+        );
+    }
+}
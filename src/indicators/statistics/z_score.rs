@@ -0,0 +1,94 @@
+use crate::{indicators::VarianceKind, *};
+use anyhow::Error;
+
+pub struct ZScoreFactory {
+    window_size: usize,
+    variance_kind: VarianceKind,
+}
+
+/// Number of standard deviations between the latest close and the mean of the window. Returns
+/// zero while the window has no spread
+#[derive(Clone, Debug)]
+pub struct ZScore {
+    average: indicators::SMA,
+    variance_kind: VarianceKind,
+}
+
+impl ZScoreFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            variance_kind: VarianceKind::Population,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_variance_kind(mut self, variance_kind: VarianceKind) -> Self {
+        self.variance_kind = variance_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<ZScore, Error> {
+        Ok(ZScore {
+            average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+            variance_kind: self.variance_kind,
+        })
+    }
+}
+
+impl<T: Close> Indicator<T> for ZScore {
+    type Output = f64;
+    fn next(&mut self, next: T) -> Self::Output {
+        let new = next.close();
+        let mean = self.average.next(new);
+        let deviation =
+            indicators::MovingAverage::<f64>::variance(&self.average, &self.variance_kind)
+                .unwrap()
+                .sqrt();
+        if deviation == 0.0 {
+            return 0.0;
+        }
+        (new - mean) / deviation
+    }
+}
+
+impl<T: Close> indicators::Statistic<T> for ZScore {}
+
+impl ZScore {
+    pub fn factory() -> ZScoreFactory {
+        ZScoreFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::VarianceKind, indicators::ZScore, Indicator};
+
+    #[test]
+    fn test_build() {
+        ZScore::factory()
+            .with_window_size(20)
+            .with_variance_kind(VarianceKind::Sample)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut z_score = ZScore::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(z_score.next(10.0), 0.0);
+        assert_eq!(z_score.next(20.0), 1.0);
+        assert_eq!(z_score.next(15.0), 0.0);
+        assert_eq!(z_score.next(10.0), -0.9045340337332908);
+        assert_eq!(z_score.next(10.0), -0.75);
+        assert_eq!(z_score.next(10.0), -0.75);
+        assert_eq!(z_score.next(12.5), 0.5);
+    }
+}
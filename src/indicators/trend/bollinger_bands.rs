@@ -9,6 +9,7 @@ where
     middle_band: T,
 }
 
+#[derive(Clone, Debug)]
 pub struct BollingerBand<T>
 where
     T: indicators::MovingAverage<f64> + Clone,
@@ -18,7 +19,7 @@ where
     standard_deviation_indicator: SD<T>,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct BollingerBandResult {
     pub upper: f64,
     pub middle: f64,
@@ -136,9 +137,9 @@ mod tests {
         assert_eq!(
             bollinger_band.next(15.0),
             BollingerBandResult {
-                upper: 23.164965809277255,
+                upper: 23.164965809277263,
                 middle: 15.0,
-                lower: 6.835034190722743,
+                lower: 6.835034190722739,
             }
         );
 
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use crate::*;
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct CoppockCurveFactory<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    long_roc: usize,
+    short_roc: usize,
+    moving_average: T,
+}
+
+/// Coppock curve: a moving average of the sum of a long and a short rate of change, in percent.
+/// Returns `None` until the long rate of change is available
+#[derive(Clone, Debug)]
+pub struct CoppockCurve<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    long_roc: usize,
+    short_roc: usize,
+    moving_average: T,
+    history: VecDeque<f64>,
+}
+
+impl<T: indicators::MovingAverage<f64>> CoppockCurveFactory<T> {
+    pub fn new() -> CoppockCurveFactory<indicators::WMA> {
+        CoppockCurveFactory {
+            long_roc: 14,
+            short_roc: 11,
+            moving_average: indicators::WMA::factory()
+                .with_window_size(10)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    pub fn with_long_roc(mut self, long_roc: usize) -> Self {
+        self.long_roc = long_roc;
+        self
+    }
+
+    pub fn with_short_roc(mut self, short_roc: usize) -> Self {
+        self.short_roc = short_roc;
+        self
+    }
+
+    pub fn with_moving_average<U: indicators::MovingAverage<f64>>(
+        self,
+        moving_average: U,
+    ) -> CoppockCurveFactory<U> {
+        CoppockCurveFactory {
+            long_roc: self.long_roc,
+            short_roc: self.short_roc,
+            moving_average,
+        }
+    }
+
+    pub fn build(self) -> Result<CoppockCurve<T>, Error> {
+        check_window_size(self.long_roc)?;
+        check_window_size(self.short_roc)?;
+        if self.short_roc >= self.long_roc {
+            return Err(anyhow!(
+                "Long rate of change period must be greater than the short one. You used {} and {}",
+                self.long_roc,
+                self.short_roc
+            ));
+        }
+        Ok(CoppockCurve {
+            long_roc: self.long_roc,
+            short_roc: self.short_roc,
+            moving_average: self.moving_average,
+            history: VecDeque::with_capacity(self.long_roc + 1),
+        })
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> Indicator<U> for CoppockCurve<T> {
+    type Output = Option<f64>;
+    fn next(&mut self, next: U) -> Self::Output {
+        let new = next.close();
+        self.history.push_back(new);
+        if self.history.len() <= self.long_roc {
+            return None;
+        }
+        let long_base = self.history.pop_front().unwrap();
+        let short_base = self.history[self.long_roc - self.short_roc - 1];
+        let roc_sum =
+            (new - long_base) / long_base * 100.0 + (new - short_base) / short_base * 100.0;
+        Some(self.moving_average.next(roc_sum))
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> indicators::Trend<U> for CoppockCurve<T> {}
+
+impl<T: indicators::MovingAverage<f64>> CoppockCurve<T> {
+    pub fn factory() -> CoppockCurveFactory<indicators::WMA> {
+        CoppockCurveFactory::<T>::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::CoppockCurve, indicators::WMA, Indicator};
+
+    #[test]
+    fn test_build() {
+        CoppockCurve::<WMA>::factory()
+            .with_long_roc(14)
+            .with_short_roc(11)
+            .with_moving_average(WMA::factory().with_window_size(10).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert!(CoppockCurve::<WMA>::factory()
+            .with_long_roc(5)
+            .with_short_roc(10)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut coppock = CoppockCurve::<WMA>::factory()
+            .with_long_roc(3)
+            .with_short_roc(2)
+            .with_moving_average(WMA::factory().with_window_size(2).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(coppock.next(10.0), None);
+        assert_eq!(coppock.next(20.0), None);
+        assert_eq!(coppock.next(15.0), None);
+        assert_eq!(coppock.next(10.0), Some(-50.0));
+        assert_eq!(coppock.next(10.0), Some(-72.22222222222221));
+        assert_eq!(coppock.next(10.0), Some(-50.0));
+        assert_eq!(coppock.next(12.5), Some(22.22222222222221));
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use crate::*;
+use anyhow::Error;
+
+pub struct DpoFactory<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    moving_average: T,
+    displacement: Option<usize>,
+}
+
+/// Detrended Price Oscillator: the close `displacement` bars ago minus the current moving
+/// average. The displacement defaults to `window_size / 2 + 1`, which centers the average on the
+/// displaced close. Returns `None` until `displacement` bars of history are available
+#[derive(Clone, Debug)]
+pub struct DPO<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    moving_average: T,
+    displacement: usize,
+    history: VecDeque<f64>,
+}
+
+impl<T: indicators::MovingAverage<f64>> DpoFactory<T> {
+    pub fn new() -> DpoFactory<indicators::SMA> {
+        DpoFactory {
+            moving_average: indicators::SMA::factory()
+                .with_window_size(20)
+                .build()
+                .unwrap(),
+            displacement: None,
+        }
+    }
+
+    pub fn with_moving_average<U: indicators::MovingAverage<f64>>(
+        self,
+        moving_average: U,
+    ) -> DpoFactory<U> {
+        DpoFactory {
+            moving_average,
+            displacement: self.displacement,
+        }
+    }
+
+    pub fn with_displacement(mut self, displacement: usize) -> Self {
+        self.displacement = Some(displacement);
+        self
+    }
+
+    pub fn build(self) -> Result<DPO<T>, Error> {
+        let displacement = self
+            .displacement
+            .unwrap_or(self.moving_average.window_size() / 2 + 1);
+        Ok(DPO {
+            moving_average: self.moving_average,
+            displacement,
+            history: VecDeque::with_capacity(displacement + 1),
+        })
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> Indicator<U> for DPO<T> {
+    type Output = Option<f64>;
+    fn next(&mut self, next: U) -> Self::Output {
+        let new = next.close();
+        let average = self.moving_average.next(new);
+
+        self.history.push_back(new);
+        if self.history.len() <= self.displacement {
+            return None;
+        }
+        let displaced = self.history.pop_front().unwrap();
+        Some(displaced - average)
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> indicators::Trend<U> for DPO<T> {}
+
+impl<T: indicators::MovingAverage<f64>> DPO<T> {
+    pub fn factory() -> DpoFactory<indicators::SMA> {
+        DpoFactory::<T>::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::DPO, indicators::SMA, Indicator};
+
+    #[test]
+    fn test_build() {
+        DPO::<SMA>::factory()
+            .with_moving_average(SMA::factory().build().unwrap())
+            .with_displacement(3)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dpo = DPO::<SMA>::factory()
+            .with_moving_average(SMA::factory().with_window_size(5).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(dpo.next(10.0), None);
+        assert_eq!(dpo.next(20.0), None);
+        assert_eq!(dpo.next(15.0), None);
+        assert_eq!(dpo.next(10.0), Some(-3.75));
+        assert_eq!(dpo.next(10.0), Some(7.0));
+        assert_eq!(dpo.next(10.0), Some(2.0));
+        assert_eq!(dpo.next(12.5), Some(-1.5));
+    }
+}
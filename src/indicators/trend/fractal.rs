@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use anyhow::{anyhow, Error};
+
+use crate::{
+    helpers::check_window_size,
+    indicators::{SwingKind, SwingPoint},
+    *,
+};
+
+/// Fractals confirmed on a bar. Both may be set when the middle bar of the window is an outside
+/// bar
+#[derive(Clone, PartialEq, Debug)]
+pub struct FractalResult {
+    pub high: Option<SwingPoint>,
+    pub low: Option<SwingPoint>,
+}
+
+pub struct FractalFactory {
+    window_size: usize,
+}
+
+/// Bill Williams fractals. A bar is a swing high (low) when its high (low) is strictly above
+/// (below) that of every other bar in a window centered on it, so each fractal is confirmed
+/// `window_size / 2` bars after it occurs
+#[derive(Clone, Debug)]
+pub struct Fractal {
+    window_size: usize,
+    index: usize,
+    window: VecDeque<(f64, f64)>,
+}
+
+impl FractalFactory {
+    pub fn new() -> Self {
+        Self { window_size: 5 }
+    }
+
+    /// the number of bars in the window, including the middle bar. Must be odd
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<Fractal, Error> {
+        check_window_size(self.window_size)?;
+        if self.window_size.is_multiple_of(2) {
+            return Err(anyhow!(
+                "Window size must be odd. You used size {}",
+                self.window_size
+            ));
+        }
+        Ok(Fractal {
+            window_size: self.window_size,
+            index: 0,
+            window: VecDeque::with_capacity(self.window_size),
+        })
+    }
+}
+
+impl Default for FractalFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: High + Low> Indicator<T> for Fractal {
+    type Output = FractalResult;
+    fn next(&mut self, next: T) -> Self::Output {
+        if self.window.len() == self.window_size {
+            self.window.pop_front();
+        }
+        self.window.push_back((next.high(), next.low()));
+        self.index += 1;
+
+        if self.window.len() < self.window_size {
+            return FractalResult {
+                high: None,
+                low: None,
+            };
+        }
+
+        let middle = self.window_size / 2;
+        let (high, low) = self.window[middle];
+        let others = || {
+            self.window
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != middle)
+                .map(|(_, bar)| bar)
+        };
+        let index = self.index - 1 - middle;
+
+        FractalResult {
+            high: others()
+                .all(|(other, _)| *other < high)
+                .then_some(SwingPoint {
+                    kind: SwingKind::High,
+                    price: high,
+                    index,
+                }),
+            low: others()
+                .all(|(_, other)| *other > low)
+                .then_some(SwingPoint {
+                    kind: SwingKind::Low,
+                    price: low,
+                    index,
+                }),
+        }
+    }
+}
+
+impl<T: High + Low> indicators::Trend<T> for Fractal {}
+
+impl Fractal {
+    pub fn factory() -> FractalFactory {
+        FractalFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{Fractal, FractalResult, SwingKind, SwingPoint},
+        Candle, Indicator,
+    };
+
+    fn bar(high: f64, low: f64) -> Candle {
+        Candle::new(low, high, high, low, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        Fractal::factory().with_window_size(7).build().unwrap();
+        assert!(Fractal::factory().with_window_size(4).build().is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut fractal = Fractal::factory().build().unwrap();
+        let none = FractalResult {
+            high: None,
+            low: None,
+        };
+
+        assert_eq!(fractal.next(bar(10.0, 5.0)), none);
+        assert_eq!(fractal.next(bar(11.0, 6.0)), none);
+        assert_eq!(fractal.next(bar(13.0, 4.0)), none);
+        assert_eq!(fractal.next(bar(12.0, 7.0)), none);
+        assert_eq!(
+            fractal.next(bar(11.0, 6.0)),
+            FractalResult {
+                high: Some(SwingPoint {
+                    kind: SwingKind::High,
+                    price: 13.0,
+                    index: 2,
+                }),
+                low: Some(SwingPoint {
+                    kind: SwingKind::Low,
+                    price: 4.0,
+                    index: 2,
+                }),
+            }
+        );
+        assert_eq!(fractal.next(bar(10.0, 8.0)), none);
+        assert_eq!(fractal.next(bar(12.0, 9.0)), none);
+        assert_eq!(fractal.next(bar(9.0, 7.0)), none);
+        assert_eq!(
+            fractal.next(bar(8.0, 7.5)),
+            FractalResult {
+                high: Some(SwingPoint {
+                    kind: SwingKind::High,
+                    price: 12.0,
+                    index: 6,
+                }),
+                low: None,
+            }
+        );
+    }
+}
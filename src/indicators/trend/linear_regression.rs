@@ -0,0 +1,333 @@
+use crate::*;
+use anyhow::{anyhow, Error};
+use helpers::*;
+
+pub struct LinearRegressionFactory {
+    window_size: usize,
+    deviations: f64,
+}
+
+/// Least squares fit over the window, with `x` counted in bars from the oldest value in the
+/// window. `value` is the fitted value on the latest bar (the LSMA), `forecast` is the fitted
+/// value one bar ahead, and `upper`/`lower` are `value` plus or minus the configured number of
+/// standard errors
+#[derive(Clone, PartialEq, Debug)]
+pub struct LinearRegressionResult {
+    pub slope: f64,
+    pub intercept: f64,
+    pub r_squared: f64,
+    pub value: f64,
+    pub forecast: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LinearRegression {
+    window_size: usize,
+    deviations: f64,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    // the sums are of values less this shift, close to the window's mean, so the variance terms
+    // don't cancel at large price levels
+    shift: f64,
+    sum_y: f64,
+    sum_xy: f64,
+    sum_y2: f64,
+}
+
+impl LinearRegressionFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            deviations: 2.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// number of standard errors between the regression line and the channel bounds
+    pub fn with_deviations(mut self, deviations: f64) -> Self {
+        self.deviations = deviations;
+        self
+    }
+
+    pub fn build(self) -> Result<LinearRegression, Error> {
+        check_window_size(self.window_size)?;
+        if self.deviations < 0.0 || !self.deviations.is_finite() {
+            return Err(anyhow!(
+                "Deviations must be positive and a real number. You used {}",
+                self.deviations
+            ));
+        }
+        Ok(LinearRegression {
+            window_size: self.window_size,
+            deviations: self.deviations,
+            index: 0,
+            count: 0,
+            deque: vec![0.0; self.window_size].into_boxed_slice(),
+            shift: 0.0,
+            sum_y: 0.0,
+            sum_xy: 0.0,
+            sum_y2: 0.0,
+        })
+    }
+}
+
+impl Default for LinearRegressionFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Close> Indicator<T> for LinearRegression {
+    type Output = LinearRegressionResult;
+    fn next(&mut self, next: T) -> Self::Output {
+        let close = next.close();
+        if self.count == 0 {
+            self.shift = close;
+        }
+        let new = close - self.shift;
+        if self.count < self.window_size {
+            self.sum_xy += self.count as f64 * new;
+            self.count += 1;
+        } else {
+            // every remaining value moves one bar closer to the start of the window
+            let old = self.deque[self.index] - self.shift;
+            self.sum_xy += (self.window_size - 1) as f64 * new - (self.sum_y - old);
+            self.sum_y -= old;
+            self.sum_y2 -= old * old;
+        }
+        self.sum_y += new;
+        self.sum_y2 += new * new;
+        self.deque[self.index] = close;
+        self.index = if self.index + 1 < self.window_size {
+            self.index + 1
+        } else {
+            0
+        };
+        if self.index == 0 && self.count == self.window_size {
+            self.recompute();
+        }
+
+        let n = self.count as f64;
+        let sum_x = n * (n - 1.0) / 2.0;
+        let sum_x2 = (n - 1.0) * n * (2.0 * n - 1.0) / 6.0;
+        let sxx = sum_x2 - sum_x * sum_x / n;
+        let sxy = self.sum_xy - sum_x * self.sum_y / n;
+        let syy = (self.sum_y2 - self.sum_y * self.sum_y / n).max(0.0);
+
+        let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+        let intercept = (self.sum_y - slope * sum_x) / n + self.shift;
+        let r_squared = if syy > 0.0 && sxx > 0.0 {
+            (sxy * sxy / (sxx * syy)).min(1.0)
+        } else {
+            1.0
+        };
+        let standard_error = if self.count > 2 {
+            ((syy - slope * sxy).max(0.0) / (n - 2.0)).sqrt()
+        } else {
+            0.0
+        };
+        let value = slope.mul_add(n - 1.0, intercept);
+
+        LinearRegressionResult {
+            slope,
+            intercept,
+            r_squared,
+            value,
+            forecast: slope.mul_add(n, intercept),
+            upper: value + standard_error * self.deviations,
+            lower: value - standard_error * self.deviations,
+        }
+    }
+}
+
+impl<T: Close> indicators::Trend<T> for LinearRegression {}
+
+impl LinearRegression {
+    pub fn factory() -> LinearRegressionFactory {
+        LinearRegressionFactory::new()
+    }
+
+    pub(crate) fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    // As in `SMA`, the running sums are rebuilt from the window each time it wraps around, so
+    // their rounding error stays bounded by a single window of updates. The oldest value is then
+    // at the start of the window
+    fn recompute(&mut self) {
+        self.shift = self.deque.iter().sum::<f64>() / self.window_size as f64;
+        self.sum_y = 0.0;
+        self.sum_xy = 0.0;
+        self.sum_y2 = 0.0;
+        for (x, y) in self.deque.iter().enumerate() {
+            let y = y - self.shift;
+            self.sum_y += y;
+            self.sum_xy += x as f64 * y;
+            self.sum_y2 += y * y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{LinearRegression, LinearRegressionResult},
+        Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        LinearRegression::factory()
+            .with_window_size(20)
+            .with_deviations(1.5)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_line() {
+        let mut regression = LinearRegression::factory()
+            .with_window_size(4)
+            .build()
+            .unwrap();
+
+        for x in 0..10 {
+            let result = regression.next(2.0 * x as f64 + 1.0);
+            if x > 0 {
+                assert_eq!(result.slope, 2.0);
+                assert_eq!(result.r_squared, 1.0);
+            }
+            assert_eq!(result.value, 2.0 * x as f64 + 1.0);
+            assert_eq!(result.upper, result.value);
+        }
+    }
+
+    #[test]
+    fn test_large_offset() {
+        let mut regression = LinearRegression::factory()
+            .with_window_size(10)
+            .build()
+            .unwrap();
+        let mut reference = LinearRegression::factory()
+            .with_window_size(10)
+            .build()
+            .unwrap();
+
+        // cent moves at a price of 50 000, over a long stream
+        for i in 0..100_000 {
+            let price = ((i * 7919) % 13) as f64 * 0.01 - 0.06;
+            let result = regression.next(50_000.0 + price);
+            let expected = reference.next(price);
+            assert!((result.slope - expected.slope).abs() < 1e-9);
+            assert!((result.r_squared - expected.r_squared).abs() < 1e-6);
+            assert!((result.value - 50_000.0 - expected.value).abs() < 1e-9);
+            assert!((result.upper - result.lower - (expected.upper - expected.lower)).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let mut regression = LinearRegression::factory()
+            .with_window_size(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            regression.next(10.0),
+            LinearRegressionResult {
+                slope: 0.0,
+                intercept: 10.0,
+                r_squared: 1.0,
+                value: 10.0,
+                forecast: 10.0,
+                upper: 10.0,
+                lower: 10.0,
+            }
+        );
+
+        assert_eq!(
+            regression.next(20.0),
+            LinearRegressionResult {
+                slope: 10.0,
+                intercept: 10.0,
+                r_squared: 1.0,
+                value: 20.0,
+                forecast: 30.0,
+                upper: 20.0,
+                lower: 20.0,
+            }
+        );
+
+        assert_eq!(
+            regression.next(15.0),
+            LinearRegressionResult {
+                slope: 2.5,
+                intercept: 12.5,
+                r_squared: 0.25,
+                value: 17.5,
+                forecast: 20.0,
+                upper: 29.74744871391589,
+                lower: 5.25255128608411,
+            }
+        );
+
+        assert_eq!(
+            regression.next(10.0),
+            LinearRegressionResult {
+                slope: -0.5,
+                intercept: 14.5,
+                r_squared: 0.01818181818181818,
+                value: 13.0,
+                forecast: 12.5,
+                upper: 24.618950038622252,
+                lower: 1.3810499613777498,
+            }
+        );
+
+        assert_eq!(
+            regression.next(10.0),
+            LinearRegressionResult {
+                slope: -1.0,
+                intercept: 15.0,
+                r_squared: 0.125,
+                value: 11.0,
+                forecast: 10.0,
+                upper: 20.66091783079296,
+                lower: 1.3390821692070407,
+            }
+        );
+
+        assert_eq!(
+            regression.next(10.0),
+            LinearRegressionResult {
+                slope: -2.5,
+                intercept: 18.0,
+                r_squared: 0.78125,
+                value: 8.0,
+                forecast: 5.5,
+                upper: 12.83045891539648,
+                lower: 3.1695410846035204,
+            }
+        );
+
+        assert_eq!(
+            regression.next(12.5),
+            LinearRegressionResult {
+                slope: -0.5,
+                intercept: 12.5,
+                r_squared: 0.125,
+                value: 10.5,
+                forecast: 10.0,
+                upper: 15.33045891539648,
+                lower: 5.66954108460352,
+            }
+        );
+    }
+}
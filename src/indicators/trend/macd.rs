@@ -12,13 +12,14 @@ where
     signal_ma: V,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct MacdResult {
     pub macd: f64,
     pub signal: f64,
     pub histogram: f64,
 }
 
+#[derive(Clone, Debug)]
 pub struct MACD<T, U, V>
 where
     T: indicators::MovingAverage<f64>,
@@ -1,14 +1,33 @@
 mod bollinger_bands;
 pub use bollinger_bands::*;
 
+mod coppock_curve;
+pub use coppock_curve::*;
+
+mod dpo;
+pub use dpo::*;
+
+mod fractal;
+pub use fractal::*;
+
+mod linear_regression;
+pub use linear_regression::*;
+
 mod macd;
 pub use macd::*;
 
+mod pivot_point;
+pub use pivot_point::*;
+
 pub mod psar;
 pub use psar::*;
 
 mod rsi;
 pub use rsi::RSI;
 
+#[allow(clippy::module_inception)]
 mod trend;
 pub use trend::Trend;
+
+mod zigzag;
+pub use zigzag::*;
@@ -0,0 +1,245 @@
+use anyhow::Error;
+
+use crate::*;
+
+/// formula used to derive the pivot levels from the previous period's candle
+#[derive(Clone, Debug, PartialEq)]
+pub enum PivotPointMethod {
+    Classic,
+    Fibonacci,
+    Camarilla,
+    Woodie,
+    DeMark,
+}
+
+/// Pivot levels for the upcoming period. DeMark pivots only define the first support and
+/// resistance levels, so `r2`, `r3`, `s2` and `s3` are `NAN` for that method
+#[derive(Clone, PartialEq, Debug)]
+pub struct PivotPointResult {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+pub struct PivotPointFactory {
+    method: PivotPointMethod,
+}
+
+/// `PivotPoint` expects each input to be a completed period candle (e.g. yesterday's daily
+/// candle), and returns the levels to plot over the following period
+#[derive(Clone, Debug)]
+pub struct PivotPoint {
+    method: PivotPointMethod,
+}
+
+impl PivotPointFactory {
+    pub fn new() -> Self {
+        Self {
+            method: PivotPointMethod::Classic,
+        }
+    }
+
+    pub fn with_method(mut self, method: PivotPointMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn build(self) -> Result<PivotPoint, Error> {
+        Ok(PivotPoint {
+            method: self.method,
+        })
+    }
+}
+
+impl Default for PivotPointFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Open + High + Low + Close> Indicator<T> for PivotPoint {
+    type Output = PivotPointResult;
+    fn next(&mut self, next: T) -> Self::Output {
+        let (open, high, low, close) = (next.open(), next.high(), next.low(), next.close());
+        let range = high - low;
+
+        match self.method {
+            PivotPointMethod::Classic => {
+                let pivot = (high + low + close) / 3.0;
+                PivotPointResult {
+                    pivot,
+                    r1: 2.0 * pivot - low,
+                    r2: pivot + range,
+                    r3: high + 2.0 * (pivot - low),
+                    s1: 2.0 * pivot - high,
+                    s2: pivot - range,
+                    s3: low - 2.0 * (high - pivot),
+                }
+            }
+            PivotPointMethod::Fibonacci => {
+                let pivot = (high + low + close) / 3.0;
+                PivotPointResult {
+                    pivot,
+                    r1: pivot + 0.382 * range,
+                    r2: pivot + 0.618 * range,
+                    r3: pivot + range,
+                    s1: pivot - 0.382 * range,
+                    s2: pivot - 0.618 * range,
+                    s3: pivot - range,
+                }
+            }
+            PivotPointMethod::Camarilla => PivotPointResult {
+                pivot: (high + low + close) / 3.0,
+                r1: close + range * 1.1 / 12.0,
+                r2: close + range * 1.1 / 6.0,
+                r3: close + range * 1.1 / 4.0,
+                s1: close - range * 1.1 / 12.0,
+                s2: close - range * 1.1 / 6.0,
+                s3: close - range * 1.1 / 4.0,
+            },
+            PivotPointMethod::Woodie => {
+                let pivot = (high + low + 2.0 * close) / 4.0;
+                PivotPointResult {
+                    pivot,
+                    r1: 2.0 * pivot - low,
+                    r2: pivot + range,
+                    r3: high + 2.0 * (pivot - low),
+                    s1: 2.0 * pivot - high,
+                    s2: pivot - range,
+                    s3: low - 2.0 * (high - pivot),
+                }
+            }
+            PivotPointMethod::DeMark => {
+                let x = if close < open {
+                    high + 2.0 * low + close
+                } else if close > open {
+                    2.0 * high + low + close
+                } else {
+                    high + low + 2.0 * close
+                };
+                PivotPointResult {
+                    pivot: x / 4.0,
+                    r1: x / 2.0 - low,
+                    r2: f64::NAN,
+                    r3: f64::NAN,
+                    s1: x / 2.0 - high,
+                    s2: f64::NAN,
+                    s3: f64::NAN,
+                }
+            }
+        }
+    }
+}
+
+impl<T: Open + High + Low + Close> indicators::Trend<T> for PivotPoint {}
+
+impl PivotPoint {
+    pub fn factory() -> PivotPointFactory {
+        PivotPointFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{PivotPoint, PivotPointMethod, PivotPointResult},
+        Candle, Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        PivotPoint::factory()
+            .with_method(PivotPointMethod::Woodie)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let candle = Candle::new(12.0, 15.0, 20.0, 10.0, 100.0).unwrap();
+
+        let mut classic = PivotPoint::factory().build().unwrap();
+        assert_eq!(
+            classic.next(candle.clone()),
+            PivotPointResult {
+                pivot: 15.0,
+                r1: 20.0,
+                r2: 25.0,
+                r3: 30.0,
+                s1: 10.0,
+                s2: 5.0,
+                s3: 0.0,
+            }
+        );
+
+        let mut fibonacci = PivotPoint::factory()
+            .with_method(PivotPointMethod::Fibonacci)
+            .build()
+            .unwrap();
+        assert_eq!(
+            fibonacci.next(candle.clone()),
+            PivotPointResult {
+                pivot: 15.0,
+                r1: 18.82,
+                r2: 21.18,
+                r3: 25.0,
+                s1: 11.18,
+                s2: 8.82,
+                s3: 5.0,
+            }
+        );
+
+        let mut camarilla = PivotPoint::factory()
+            .with_method(PivotPointMethod::Camarilla)
+            .build()
+            .unwrap();
+        assert_eq!(
+            camarilla.next(candle.clone()),
+            PivotPointResult {
+                pivot: 15.0,
+                r1: 15.916666666666666,
+                r2: 16.833333333333332,
+                r3: 17.75,
+                s1: 14.083333333333334,
+                s2: 13.166666666666666,
+                s3: 12.25,
+            }
+        );
+
+        let mut woodie = PivotPoint::factory()
+            .with_method(PivotPointMethod::Woodie)
+            .build()
+            .unwrap();
+        assert_eq!(
+            woodie.next(Candle::new(12.0, 18.0, 20.0, 10.0, 100.0).unwrap()),
+            PivotPointResult {
+                pivot: 16.5,
+                r1: 23.0,
+                r2: 26.5,
+                r3: 33.0,
+                s1: 13.0,
+                s2: 6.5,
+                s3: 3.0,
+            }
+        );
+
+        let mut demark = PivotPoint::factory()
+            .with_method(PivotPointMethod::DeMark)
+            .build()
+            .unwrap();
+        let result = demark.next(candle);
+        assert_eq!(result.pivot, 16.25);
+        assert_eq!(result.r1, 22.5);
+        assert_eq!(result.s1, 12.5);
+        assert!(result.r2.is_nan() && result.s3.is_nan());
+
+        let result = demark.next(Candle::new(18.0, 15.0, 20.0, 10.0, 100.0).unwrap());
+        assert_eq!(result.pivot, 13.75);
+        assert_eq!(result.r1, 17.5);
+        assert_eq!(result.s1, 7.5);
+    }
+}
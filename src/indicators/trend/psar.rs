@@ -8,53 +8,42 @@ pub enum PsarTrend {
     Down,
 }
 
-#[derive(Clone, Debug)]
-struct CHL {
-    close: f64,
-    high: f64,
-    low: f64,
-}
-
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct PsarResult {
     pub trend: PsarTrend,
     pub sar: f64,
-}
-
-impl Close for CHL {
-    fn close(&self) -> f64 {
-        self.close
-    }
-}
-
-impl High for CHL {
-    fn high(&self) -> f64 {
-        self.high
-    }
-}
-
-impl Low for CHL {
-    fn low(&self) -> f64 {
-        self.low
-    }
+    /// `true` on the bar where the trend flipped
+    pub reversed: bool,
+    /// highest high of an up trend, or lowest low of a down trend
+    pub extreme_point: f64,
+    /// acceleration factor used to advance the SAR on this bar
+    pub accel: f64,
 }
 
 pub struct PsarFactory {
     af_max: f64,
     af_step: f64,
+    af_start: Option<f64>,
+    seed_period: Option<usize>,
+    initial_trend: Option<PsarTrend>,
 }
 
 #[derive(Clone, Debug)]
 pub struct PSAR {
     max_accel: f64,
     accel_step: f64,
+    start_accel: f64,
+    seed_period: usize,
+    seen: usize,
+    first_close: f64,
+    initial_trend: Option<PsarTrend>,
     trend: PsarTrend,
     trend_inc: u32,
     low: f64,
     high: f64,
     sar: f64,
-    prev_candle: CHL,
-    prev_trend: PsarTrend,
+    prev_high: f64,
+    prev_low: f64,
 }
 
 impl PsarFactory {
@@ -62,6 +51,9 @@ impl PsarFactory {
         Self {
             af_max: 0.2,
             af_step: 0.02,
+            af_start: None,
+            seed_period: None,
+            initial_trend: None,
         }
     }
 
@@ -75,35 +67,83 @@ impl PsarFactory {
         self
     }
 
+    /// acceleration factor used on the first bar of each trend. Defaults to the acceleration step
+    pub fn with_start_accel(mut self, start: f64) -> Self {
+        self.af_start = Some(start);
+        self
+    }
+
+    /// determine the initial trend from the first `seed_period` bars, by comparing the last close
+    /// of the period against the first one. Defaults to two bars. Cannot be combined with an
+    /// initial trend
+    pub fn with_seed_period(mut self, seed_period: usize) -> Self {
+        self.seed_period = Some(seed_period);
+        self
+    }
+
+    /// start in the given trend from the first bar, instead of seeding it. Cannot be combined
+    /// with a seed period
+    pub fn with_initial_trend(mut self, trend: PsarTrend) -> Self {
+        self.initial_trend = Some(trend);
+        self
+    }
+
     pub fn build(self) -> Result<PSAR, Error> {
         if self.af_step <= 0.0 || !self.af_step.is_finite() {
-            return Err(anyhow!("Max acceleration must be greater than zero, and a real number. You used {}", self.af_step));
+            return Err(anyhow!("Acceleration step must be greater than zero, and a real number. You used {}", self.af_step));
         }
         if self.af_max <= 0.0 || !self.af_max.is_finite() {
             return Err(anyhow!("Max acceleration must be greater than zero, and a real number. You used {}", self.af_max));
         }
+        let af_start = self.af_start.unwrap_or(self.af_step);
+        if af_start <= 0.0 || !af_start.is_finite() || af_start > self.af_max {
+            return Err(anyhow!("Start acceleration must be greater than zero, a real number, and no greater than the max acceleration. You used {}", af_start));
+        }
+        let seed_period = match (self.seed_period, &self.initial_trend) {
+            (Some(seed_period), Some(_)) => {
+                return Err(anyhow!("Set either a seed period or an initial trend, not both. You used a seed period of {}", seed_period));
+            }
+            (_, Some(_)) => 1,
+            (Some(seed_period), None) if seed_period < 2 => {
+                return Err(anyhow!("Seed period must be at least two. You used {}", seed_period));
+            }
+            (seed_period, None) => seed_period.unwrap_or(2),
+        };
         Ok(PSAR {
             max_accel: self.af_max,
             accel_step: self.af_step,
+            start_accel: af_start,
+            seed_period,
+            seen: 0,
+            first_close: f64::NAN,
+            initial_trend: self.initial_trend,
             trend: PsarTrend::Down,
             trend_inc: 0,
-            low: std::f64::NAN,
-            high: std::f64::NAN,
-            sar: std::f64::NAN,
-            prev_candle: CHL {
-                close: 0.0,
-                high: 0.0,
-                low: 0.0,
-            },
-            prev_trend: PsarTrend::Down,
+            low: f64::NAN,
+            high: f64::NAN,
+            sar: f64::NAN,
+            prev_high: f64::NAN,
+            prev_low: f64::NAN,
         })
     }
 }
 
+impl Default for PsarFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Close + High + Low> crate::Indicator<T> for PSAR {
     type Output = PsarResult;
 
     fn next(&mut self, next: T) -> Self::Output {
+        if self.seen < self.seed_period {
+            return self.seed(next);
+        }
+
+        let prev_trend = self.trend.clone();
+
         match self.trend {
             PsarTrend::Up => {
                 if self.high < next.high() {
@@ -131,34 +171,32 @@ impl<T: Close + High + Low> crate::Indicator<T> for PSAR {
             }
         };
 
-        let trend = self.trend.clone();
-
         let af = self
             .max_accel
-            .min(self.accel_step * (self.trend_inc as f64));
+            .min(self.accel_step * (self.trend_inc as f64) + (self.start_accel - self.accel_step));
 
-        match self.trend {
+        let extreme_point = match self.trend {
             PsarTrend::Up => {
                 self.sar = af.mul_add(self.high - self.sar, self.sar);
-                self.sar = self.sar.min(next.low()).min(self.prev_candle.low());
+                self.sar = self.sar.min(next.low()).min(self.prev_low);
+                self.high
             }
             PsarTrend::Down => {
                 self.sar = af.mul_add(self.low - self.sar, self.sar);
-                self.sar = self.sar.max(next.high()).max(self.prev_candle.high());
+                self.sar = self.sar.max(next.high()).max(self.prev_high);
+                self.low
             }
-        }
-
-        self.prev_candle = CHL {
-            close: next.close(),
-            high: next.high(),
-            low: next.low(),
         };
 
-        self.prev_trend = trend;
+        self.prev_high = next.high();
+        self.prev_low = next.low();
 
         PsarResult {
+            reversed: self.trend != prev_trend,
             trend: self.trend.clone(),
             sar: self.sar,
+            extreme_point,
+            accel: af,
         }
     }
 }
@@ -169,22 +207,106 @@ impl PSAR {
     pub fn factory() -> PsarFactory {
         PsarFactory::new()
     }
+
+    // accumulate the seed period. The SAR starts at the opposite extreme of the bars seen so far,
+    // and the trend follows the latest close against the first, reversing as it crosses it
+    fn seed<T: Close + High + Low>(&mut self, next: T) -> PsarResult {
+        if self.seen == 0 {
+            self.first_close = next.close();
+            self.high = next.high();
+            self.low = next.low();
+        } else {
+            self.high = self.high.max(next.high());
+            self.low = self.low.min(next.low());
+        }
+        self.seen += 1;
+
+        let prev_trend = self.trend.clone();
+        self.trend = match &self.initial_trend {
+            Some(trend) => trend.clone(),
+            None if next.close() >= self.first_close => PsarTrend::Up,
+            None => PsarTrend::Down,
+        };
+        self.trend_inc = 1;
+        self.prev_high = next.high();
+        self.prev_low = next.low();
+
+        let extreme_point = match self.trend {
+            PsarTrend::Up => {
+                self.sar = self.low;
+                self.high
+            }
+            PsarTrend::Down => {
+                self.sar = self.high;
+                self.low
+            }
+        };
+
+        PsarResult {
+            reversed: self.seen > 1 && self.trend != prev_trend,
+            trend: self.trend.clone(),
+            sar: self.sar,
+            extreme_point,
+            accel: self.start_accel,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        indicators::trend::psar::PsarResult, indicators::trend::psar::CHL, indicators::PSAR,
-        Indicator,
+        indicators::trend::psar::{PsarResult, PsarTrend},
+        indicators::PSAR,
+        *,
     };
 
+    #[derive(Clone, Debug)]
+    struct CHL {
+        close: f64,
+        high: f64,
+        low: f64,
+    }
+
+    impl Close for CHL {
+        fn close(&self) -> f64 {
+            self.close
+        }
+    }
+
+    impl High for CHL {
+        fn high(&self) -> f64 {
+            self.high
+        }
+    }
+
+    impl Low for CHL {
+        fn low(&self) -> f64 {
+            self.low
+        }
+    }
+
     #[test]
     fn test_build() {
         PSAR::factory()
             .with_accel_step(0.02)
+            .with_start_accel(0.02)
             .with_max_accel(0.2)
+            .with_seed_period(5)
             .build()
             .unwrap();
+
+        PSAR::factory()
+            .with_initial_trend(PsarTrend::Up)
+            .build()
+            .unwrap();
+
+        assert!(PSAR::factory().with_seed_period(1).build().is_err());
+        assert!(PSAR::factory()
+            .with_seed_period(3)
+            .with_initial_trend(PsarTrend::Up)
+            .build()
+            .is_err());
+        assert!(PSAR::factory().with_start_accel(0.3).build().is_err());
     }
 
     #[test]
@@ -197,8 +319,11 @@ mod tests {
                 low: 5.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Down,
-                sar: 15.0,
+                trend: PsarTrend::Up,
+                sar: 5.0,
+                reversed: false,
+                extreme_point: 15.0,
+                accel: 0.02,
             }
         );
 
@@ -209,8 +334,11 @@ mod tests {
                 low: 10.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Up,
+                trend: PsarTrend::Up,
                 sar: 5.0,
+                reversed: false,
+                extreme_point: 20.0,
+                accel: 0.02,
             }
         );
 
@@ -221,8 +349,11 @@ mod tests {
                 low: 15.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Up,
+                trend: PsarTrend::Up,
                 sar: 5.8,
+                reversed: false,
+                extreme_point: 25.0,
+                accel: 0.04,
             }
         );
 
@@ -233,8 +364,11 @@ mod tests {
                 low: 20.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Up,
+                trend: PsarTrend::Up,
                 sar: 7.252,
+                reversed: false,
+                extreme_point: 30.0,
+                accel: 0.06,
             }
         );
 
@@ -245,8 +379,11 @@ mod tests {
                 low: 15.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Up,
+                trend: PsarTrend::Up,
                 sar: 8.61688,
+                reversed: false,
+                extreme_point: 30.0,
+                accel: 0.06,
             }
         );
 
@@ -257,8 +394,11 @@ mod tests {
                 low: 10.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Up,
+                trend: PsarTrend::Up,
                 sar: 9.8998672,
+                reversed: false,
+                extreme_point: 30.0,
+                accel: 0.06,
             }
         );
 
@@ -269,8 +409,11 @@ mod tests {
                 low: 5.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Down,
+                trend: PsarTrend::Down,
                 sar: 29.5,
+                reversed: true,
+                extreme_point: 5.0,
+                accel: 0.02,
             }
         );
 
@@ -281,8 +424,115 @@ mod tests {
                 low: 5.0,
             }),
             PsarResult {
-                trend: crate::indicators::trend::psar::PsarTrend::Down,
+                trend: PsarTrend::Down,
                 sar: 29.01,
+                reversed: false,
+                extreme_point: 5.0,
+                accel: 0.02,
+            }
+        );
+    }
+
+    #[test]
+    fn test_initial_trend() {
+        let mut psar = PSAR::factory()
+            .with_initial_trend(PsarTrend::Down)
+            .with_start_accel(0.1)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 10.0,
+                high: 15.0,
+                low: 5.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Down,
+                sar: 15.0,
+                reversed: false,
+                extreme_point: 5.0,
+                accel: 0.1,
+            }
+        );
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 6.0,
+                high: 10.0,
+                low: 3.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Down,
+                sar: 15.0,
+                reversed: false,
+                extreme_point: 3.0,
+                accel: 0.12,
+            }
+        );
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 4.0,
+                high: 5.0,
+                low: 2.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Down,
+                sar: 13.18,
+                reversed: false,
+                extreme_point: 2.0,
+                accel: 0.14,
+            }
+        );
+    }
+
+    #[test]
+    fn test_seed() {
+        let mut psar = PSAR::factory().with_seed_period(3).build().unwrap();
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 10.0,
+                high: 15.0,
+                low: 5.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Up,
+                sar: 5.0,
+                reversed: false,
+                extreme_point: 15.0,
+                accel: 0.02,
+            }
+        );
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 8.0,
+                high: 12.0,
+                low: 4.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Down,
+                sar: 15.0,
+                reversed: true,
+                extreme_point: 4.0,
+                accel: 0.02,
+            }
+        );
+
+        assert_eq!(
+            psar.next(CHL {
+                close: 7.0,
+                high: 9.0,
+                low: 6.0,
+            }),
+            PsarResult {
+                trend: PsarTrend::Down,
+                sar: 15.0,
+                reversed: false,
+                extreme_point: 4.0,
+                accel: 0.02,
             }
         );
     }
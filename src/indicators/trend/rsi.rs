@@ -9,6 +9,7 @@ where
     down_indicator: T,
 }
 
+#[derive(Clone, Debug)]
 pub struct RSI<T>
 where
     T: indicators::MovingAverage<f64>,
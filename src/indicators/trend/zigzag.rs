@@ -0,0 +1,288 @@
+use anyhow::{anyhow, Error};
+
+use crate::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SwingKind {
+    High,
+    Low,
+}
+
+/// A confirmed swing pivot. `index` is the zero based position of the pivot bar in the input
+/// series, which will be earlier than the bar that confirmed it
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwingPoint {
+    pub kind: SwingKind,
+    pub price: f64,
+    pub index: usize,
+}
+
+/// the price move required to confirm a pivot
+#[derive(Clone, Debug, PartialEq)]
+pub enum ZigZagThreshold {
+    /// move of the given percentage of the pivot price, e.g. `5.0` for 5%
+    Percent(f64),
+    /// move of `multiplier` times the `ATR` over `window_size` bars
+    Atr { window_size: usize, multiplier: f64 },
+}
+
+pub struct ZigZagFactory {
+    threshold: ZigZagThreshold,
+}
+
+#[derive(Clone, Debug)]
+pub struct ZigZag {
+    threshold: ZigZagThreshold,
+    atr: Option<indicators::ATR>,
+    index: usize,
+    tracking: Option<SwingKind>,
+    high: f64,
+    high_index: usize,
+    low: f64,
+    low_index: usize,
+}
+
+impl ZigZagFactory {
+    pub fn new() -> Self {
+        Self {
+            threshold: ZigZagThreshold::Percent(5.0),
+        }
+    }
+
+    pub fn with_threshold(mut self, threshold: ZigZagThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn build(self) -> Result<ZigZag, Error> {
+        let atr = match self.threshold {
+            ZigZagThreshold::Percent(percent) => {
+                if percent <= 0.0 || !percent.is_finite() {
+                    return Err(anyhow!(
+                        "Percent threshold must be greater than zero and a real number. You used {}",
+                        percent
+                    ));
+                }
+                None
+            }
+            ZigZagThreshold::Atr {
+                window_size,
+                multiplier,
+            } => {
+                if multiplier <= 0.0 || !multiplier.is_finite() {
+                    return Err(anyhow!(
+                        "ATR multiplier must be greater than zero and a real number. You used {}",
+                        multiplier
+                    ));
+                }
+                Some(
+                    indicators::ATR::factory()
+                        .with_window_size(window_size)
+                        .build()?,
+                )
+            }
+        };
+        Ok(ZigZag {
+            threshold: self.threshold,
+            atr,
+            index: 0,
+            tracking: None,
+            high: f64::NAN,
+            high_index: 0,
+            low: f64::NAN,
+            low_index: 0,
+        })
+    }
+}
+
+impl Default for ZigZagFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Close + High + Low> Indicator<T> for ZigZag {
+    type Output = Option<SwingPoint>;
+    fn next(&mut self, next: T) -> Self::Output {
+        let index = self.index;
+        self.index += 1;
+        let (high, low) = (next.high(), next.low());
+        let atr = self.atr.as_mut().map_or(0.0, |atr| atr.next(next));
+        let reversal = |price: f64| match self.threshold {
+            ZigZagThreshold::Percent(percent) => price * percent / 100.0,
+            ZigZagThreshold::Atr { multiplier, .. } => atr * multiplier,
+        };
+
+        match self.tracking {
+            None => {
+                if index == 0 || high > self.high {
+                    self.high = high;
+                    self.high_index = index;
+                }
+                if index == 0 || low < self.low {
+                    self.low = low;
+                    self.low_index = index;
+                }
+                if self.high_index != index && low <= self.high - reversal(self.high) {
+                    self.tracking = Some(SwingKind::Low);
+                    self.low = low;
+                    self.low_index = index;
+                    return Some(SwingPoint {
+                        kind: SwingKind::High,
+                        price: self.high,
+                        index: self.high_index,
+                    });
+                }
+                if self.low_index != index && high >= self.low + reversal(self.low) {
+                    self.tracking = Some(SwingKind::High);
+                    self.high = high;
+                    self.high_index = index;
+                    return Some(SwingPoint {
+                        kind: SwingKind::Low,
+                        price: self.low,
+                        index: self.low_index,
+                    });
+                }
+                None
+            }
+            Some(SwingKind::High) => {
+                if high > self.high {
+                    self.high = high;
+                    self.high_index = index;
+                } else if low <= self.high - reversal(self.high) {
+                    self.tracking = Some(SwingKind::Low);
+                    self.low = low;
+                    self.low_index = index;
+                    return Some(SwingPoint {
+                        kind: SwingKind::High,
+                        price: self.high,
+                        index: self.high_index,
+                    });
+                }
+                None
+            }
+            Some(SwingKind::Low) => {
+                if low < self.low {
+                    self.low = low;
+                    self.low_index = index;
+                } else if high >= self.low + reversal(self.low) {
+                    self.tracking = Some(SwingKind::High);
+                    self.high = high;
+                    self.high_index = index;
+                    return Some(SwingPoint {
+                        kind: SwingKind::Low,
+                        price: self.low,
+                        index: self.low_index,
+                    });
+                }
+                None
+            }
+        }
+    }
+}
+
+impl<T: Close + High + Low> indicators::Trend<T> for ZigZag {}
+
+impl ZigZag {
+    pub fn factory() -> ZigZagFactory {
+        ZigZagFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{SwingKind, SwingPoint, ZigZag, ZigZagThreshold},
+        Candle, Indicator,
+    };
+
+    fn bar(high: f64, low: f64) -> Candle {
+        Candle::new(low, high, high, low, 0.0).unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        ZigZag::factory()
+            .with_threshold(ZigZagThreshold::Atr {
+                window_size: 14,
+                multiplier: 3.0,
+            })
+            .build()
+            .unwrap();
+
+        assert!(ZigZag::factory()
+            .with_threshold(ZigZagThreshold::Percent(-1.0))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut zigzag = ZigZag::factory()
+            .with_threshold(ZigZagThreshold::Percent(10.0))
+            .build()
+            .unwrap();
+
+        assert_eq!(zigzag.next(bar(100.0, 95.0)), None);
+        assert_eq!(
+            zigzag.next(bar(105.0, 100.0)),
+            Some(SwingPoint {
+                kind: SwingKind::Low,
+                price: 95.0,
+                index: 0,
+            })
+        );
+        assert_eq!(zigzag.next(bar(110.0, 104.0)), None);
+        assert_eq!(zigzag.next(bar(108.0, 100.0)), None);
+        assert_eq!(
+            zigzag.next(bar(101.0, 98.0)),
+            Some(SwingPoint {
+                kind: SwingKind::High,
+                price: 110.0,
+                index: 2,
+            })
+        );
+        assert_eq!(zigzag.next(bar(99.0, 90.0)), None);
+        assert_eq!(
+            zigzag.next(bar(100.0, 96.0)),
+            Some(SwingPoint {
+                kind: SwingKind::Low,
+                price: 90.0,
+                index: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_next_atr() {
+        let mut zigzag = ZigZag::factory()
+            .with_threshold(ZigZagThreshold::Atr {
+                window_size: 5,
+                multiplier: 2.0,
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(zigzag.next(bar(12.0, 10.0)), None);
+        assert_eq!(zigzag.next(bar(13.0, 11.0)), None);
+        // ATR is 2.2, so the rise from the low must be at least 4.4
+        assert_eq!(
+            zigzag.next(bar(16.0, 14.0)),
+            Some(SwingPoint {
+                kind: SwingKind::Low,
+                price: 10.0,
+                index: 0,
+            })
+        );
+        assert_eq!(zigzag.next(bar(15.0, 13.0)), None);
+        // ATR is 2.888, so the drop from the high must be at least 5.776
+        assert_eq!(
+            zigzag.next(bar(13.0, 10.0)),
+            Some(SwingPoint {
+                kind: SwingKind::High,
+                price: 16.0,
+                index: 2,
+            })
+        );
+    }
+}
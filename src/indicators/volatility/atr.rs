@@ -6,6 +6,7 @@ pub struct AtrFactory {
     window_size: usize,
 }
 
+#[derive(Clone, Debug)]
 pub struct ATR {
     window_size: usize,
     prev_atr: f64,
@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+
+use crate::*;
+use anyhow::Error;
+use helpers::*;
+
+pub struct ChaikinVolatilityFactory {
+    window_size: usize,
+    roc_period: usize,
+}
+
+/// Chaikin Volatility: the percentage rate of change, over `roc_period` bars, of an `EMA` of the
+/// high-low range. Returns `None` until `roc_period` bars of history are available
+#[derive(Clone, Debug)]
+pub struct ChaikinVolatility {
+    roc_period: usize,
+    range_average: indicators::EMA,
+    history: VecDeque<f64>,
+}
+
+impl ChaikinVolatilityFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 10,
+            roc_period: 10,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_roc_period(mut self, roc_period: usize) -> Self {
+        self.roc_period = roc_period;
+        self
+    }
+
+    pub fn build(self) -> Result<ChaikinVolatility, Error> {
+        check_window_size(self.roc_period)?;
+        Ok(ChaikinVolatility {
+            roc_period: self.roc_period,
+            range_average: indicators::EMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+            history: VecDeque::with_capacity(self.roc_period + 1),
+        })
+    }
+}
+
+impl<U: High + Low> Indicator<U> for ChaikinVolatility {
+    type Output = Option<f64>;
+    fn next(&mut self, next: U) -> Self::Output {
+        let average = self.range_average.next(next.high() - next.low());
+        self.history.push_back(average);
+        if self.history.len() <= self.roc_period {
+            return None;
+        }
+        let base = self.history.pop_front().unwrap();
+        Some((average - base) / base * 100.0)
+    }
+}
+
+impl<U: High + Low> indicators::Volatility<U> for ChaikinVolatility {}
+
+impl ChaikinVolatility {
+    pub fn factory() -> ChaikinVolatilityFactory {
+        ChaikinVolatilityFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::ChaikinVolatility, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        ChaikinVolatility::factory()
+            .with_window_size(10)
+            .with_roc_period(10)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut chaikin = ChaikinVolatility::factory()
+            .with_window_size(3)
+            .with_roc_period(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            chaikin.next(Candle::new(5.0, 15.0, 15.0, 5.0, 0.0).unwrap()),
+            None
+        );
+        assert_eq!(
+            chaikin.next(Candle::new(7.0, 20.0, 20.0, 7.0, 0.0).unwrap()),
+            None
+        );
+        assert_eq!(
+            chaikin.next(Candle::new(15.0, 25.0, 25.0, 15.0, 0.0).unwrap()),
+            Some(7.5)
+        );
+        assert_eq!(
+            chaikin.next(Candle::new(14.0, 30.0, 30.0, 14.0, 0.0).unwrap()),
+            Some(16.304347826086957)
+        );
+        assert_eq!(
+            chaikin.next(Candle::new(15.9, 25.0, 25.0, 15.9, 0.0).unwrap()),
+            Some(4.534883720930239)
+        );
+    }
+}
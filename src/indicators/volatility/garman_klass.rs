@@ -0,0 +1,100 @@
+use crate::*;
+use anyhow::Error;
+use helpers::*;
+
+pub struct GarmanKlassFactory {
+    window_size: usize,
+    periods_per_year: f64,
+}
+
+/// Garman–Klass volatility estimator, combining the high-low range with the open-close move
+#[derive(Clone, Debug)]
+pub struct GarmanKlass {
+    periods_per_year: f64,
+    variance_average: indicators::SMA,
+}
+
+impl GarmanKlassFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            periods_per_year: 1.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// annualises the result by the square root of this many bars per year. Defaults to one,
+    /// which leaves it per bar
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    pub fn build(self) -> Result<GarmanKlass, Error> {
+        check_periods_per_year(self.periods_per_year)?;
+        Ok(GarmanKlass {
+            periods_per_year: self.periods_per_year,
+            variance_average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<U: Open + High + Low + Close> Indicator<U> for GarmanKlass {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let range = (next.high() / next.low()).ln();
+        let body = (next.close() / next.open()).ln();
+        let term = 0.5 * range * range - (2.0 * std::f64::consts::LN_2 - 1.0) * body * body;
+        (self.variance_average.next(term).max(0.0) * self.periods_per_year).sqrt()
+    }
+}
+
+impl<U: Open + High + Low + Close> indicators::Volatility<U> for GarmanKlass {}
+
+impl GarmanKlass {
+    pub fn factory() -> GarmanKlassFactory {
+        GarmanKlassFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::GarmanKlass, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        GarmanKlass::factory()
+            .with_window_size(20)
+            .with_periods_per_year(252.0)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut garman_klass = GarmanKlass::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(
+            garman_klass.next(Candle::new(10.0, 10.5, 11.0, 9.0, 0.0).unwrap()),
+            0.1386174518653452
+        );
+        assert_eq!(
+            garman_klass.next(Candle::new(10.5, 11.0, 12.0, 10.0, 0.0).unwrap()),
+            0.1322864104721927
+        );
+        assert_eq!(
+            garman_klass.next(Candle::new(11.0, 10.4, 11.5, 10.2, 0.0).unwrap()),
+            0.11687488245702271
+        );
+        assert_eq!(
+            garman_klass.next(Candle::new(10.4, 10.0, 10.8, 9.5, 0.0).unwrap()),
+            0.09898616222507464
+        );
+    }
+}
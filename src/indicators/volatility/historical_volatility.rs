@@ -0,0 +1,186 @@
+use crate::{
+    indicators::{MovingAverage, VarianceKind, SMA},
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+/// how the return between two consecutive closes is measured
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReturnKind {
+    /// `ln(close / prev_close)`
+    Log,
+    /// `close / prev_close - 1`
+    Simple,
+}
+
+impl ReturnKind {
+    pub(crate) fn between(&self, prev: f64, next: f64) -> f64 {
+        match self {
+            ReturnKind::Log => (next / prev).ln(),
+            ReturnKind::Simple => next / prev - 1.0,
+        }
+    }
+}
+
+pub struct HistoricalVolatilityFactory {
+    window_size: usize,
+    periods_per_year: f64,
+    return_kind: ReturnKind,
+    variance_kind: VarianceKind,
+}
+
+/// Close to close volatility: the standard deviation of the last `window_size` returns, scaled
+/// by the square root of the periods per year. Defaults to log returns, sample variance and 252
+/// periods per year
+#[derive(Clone, Debug)]
+pub struct HistoricalVolatility {
+    periods_per_year: f64,
+    return_kind: ReturnKind,
+    variance_kind: VarianceKind,
+    prev_close: Option<f64>,
+    returns: SMA,
+}
+
+impl HistoricalVolatilityFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 20,
+            periods_per_year: 252.0,
+            return_kind: ReturnKind::Log,
+            variance_kind: VarianceKind::Sample,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    pub fn with_return_kind(mut self, return_kind: ReturnKind) -> Self {
+        self.return_kind = return_kind;
+        self
+    }
+
+    pub fn with_variance_kind(mut self, variance_kind: VarianceKind) -> Self {
+        self.variance_kind = variance_kind;
+        self
+    }
+
+    pub fn build(self) -> Result<HistoricalVolatility, Error> {
+        check_window_size(self.window_size)?;
+        check_periods_per_year(self.periods_per_year)?;
+        Ok(HistoricalVolatility {
+            periods_per_year: self.periods_per_year,
+            return_kind: self.return_kind,
+            variance_kind: self.variance_kind,
+            prev_close: None,
+            returns: SMA::factory().with_window_size(self.window_size).build()?,
+        })
+    }
+}
+
+impl<U: Close> Indicator<U> for HistoricalVolatility {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let new = next.close();
+        let prev = match self.prev_close.replace(new) {
+            Some(prev) => prev,
+            None => return 0.0,
+        };
+        self.returns.next(self.return_kind.between(prev, new));
+        let variance =
+            MovingAverage::<f64>::variance(&self.returns, &self.variance_kind).unwrap_or(0.0);
+        (variance * self.periods_per_year).sqrt()
+    }
+}
+
+impl<U: Close> indicators::Volatility<U> for HistoricalVolatility {}
+
+impl HistoricalVolatility {
+    pub fn factory() -> HistoricalVolatilityFactory {
+        HistoricalVolatilityFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{HistoricalVolatility, ReturnKind, VarianceKind},
+        Indicator,
+    };
+
+    #[test]
+    fn test_build() {
+        HistoricalVolatility::factory()
+            .with_window_size(30)
+            .with_periods_per_year(365.0)
+            .with_return_kind(ReturnKind::Simple)
+            .with_variance_kind(VarianceKind::Population)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sample = HistoricalVolatility::factory()
+            .with_window_size(3)
+            .with_periods_per_year(1.0)
+            .build()
+            .unwrap();
+        let mut population = HistoricalVolatility::factory()
+            .with_window_size(3)
+            .with_periods_per_year(1.0)
+            .with_return_kind(ReturnKind::Simple)
+            .with_variance_kind(VarianceKind::Population)
+            .build()
+            .unwrap();
+
+        assert_eq!(sample.next(10.0), 0.0);
+        assert_eq!(population.next(10.0), 0.0);
+        assert_eq!(sample.next(11.0), 0.0);
+        assert_eq!(population.next(11.0), 0.0);
+        assert_eq!(sample.next(10.5), 0.10028909297208422);
+        assert_eq!(population.next(10.5), 0.07272727272727275);
+        assert_eq!(sample.next(11.5), 0.08066248553930225);
+        assert_eq!(population.next(11.5), 0.06747355010621109);
+        assert_eq!(sample.next(12.0), 0.06974108242462031);
+        assert_eq!(population.next(12.0), 0.058101963668869765);
+        assert_eq!(sample.next(12.0), 0.04551725462561999);
+        assert_eq!(population.next(12.0), 0.038929757836219374);
+    }
+
+    #[test]
+    fn test_low_volatility() {
+        let mut historical_volatility = HistoricalVolatility::factory()
+            .with_periods_per_year(1.0)
+            .build()
+            .unwrap();
+
+        // a steady 1% trend whose returns vary by about 1e-8, where a sum of squares cancels
+        let mut closes = vec![100.0];
+        for i in 1..500 {
+            let ret = 0.01 + ((i * 7919) % 101) as f64 * 1e-10;
+            closes.push(closes[i - 1] * ret.exp());
+        }
+        let returns: Vec<f64> = closes
+            .windows(2)
+            .map(|pair| (pair[1] / pair[0]).ln())
+            .collect();
+        for (i, close) in closes.iter().enumerate() {
+            let result = historical_volatility.next(*close);
+            if i >= 20 {
+                let window = &returns[i - 20..i];
+                let mean = window.iter().sum::<f64>() / 20.0;
+                let expected =
+                    (window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 19.0).sqrt();
+                assert!((result - expected).abs() < expected * 1e-6);
+            }
+        }
+    }
+}
@@ -1,11 +1,39 @@
 mod atr;
 pub use atr::ATR;
 
+mod chaikin_volatility;
+pub use chaikin_volatility::ChaikinVolatility;
+
+mod garman_klass;
+pub use garman_klass::GarmanKlass;
+
+mod historical_volatility;
+pub use historical_volatility::{HistoricalVolatility, ReturnKind};
+
+mod natr;
+pub use natr::NATR;
+
+mod parkinson;
+pub use parkinson::Parkinson;
+
+mod rogers_satchell;
+pub use rogers_satchell::RogersSatchell;
+
+mod rvi;
+pub use rvi::RVI;
+
 mod standard_deviation;
 pub use standard_deviation::SD;
 
 mod tr;
 pub use tr::TR;
 
+mod ulcer_index;
+pub use ulcer_index::UlcerIndex;
+
+#[allow(clippy::module_inception)]
 mod volatility;
 pub use volatility::Volatility;
+
+mod yang_zhang;
+pub use yang_zhang::YangZhang;
@@ -0,0 +1,75 @@
+use crate::*;
+use anyhow::Error;
+
+pub struct NatrFactory {
+    window_size: usize,
+}
+
+/// Normalized ATR: the `ATR` as a percentage of the close
+#[derive(Clone, Debug)]
+pub struct NATR {
+    atr: indicators::ATR,
+}
+
+impl NatrFactory {
+    pub fn new() -> Self {
+        NatrFactory { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<NATR, Error> {
+        Ok(NATR {
+            atr: indicators::ATR::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<U: Close + High + Low> Indicator<U> for NATR {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let close = next.close();
+        self.atr.next(next) / close * 100.0
+    }
+}
+
+impl<U: Close + High + Low> indicators::Volatility<U> for NATR {}
+
+impl NATR {
+    pub fn factory() -> NatrFactory {
+        NatrFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::NATR, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        NATR::factory().with_window_size(14).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut natr = NATR::factory().with_window_size(5).build().unwrap();
+
+        assert_eq!(
+            natr.next(Candle::new(8.0, 8.0, 15.0, 5.0, 0.0).unwrap()),
+            125.0
+        );
+        assert_eq!(
+            natr.next(Candle::new(15.0, 15.0, 20.0, 7.0, 0.0).unwrap()),
+            70.66666666666667
+        );
+        assert_eq!(
+            natr.next(Candle::new(22.0, 20.0, 25.0, 15.0, 0.0).unwrap()),
+            52.400000000000006
+        );
+    }
+}
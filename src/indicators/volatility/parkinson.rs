@@ -0,0 +1,109 @@
+use crate::*;
+use anyhow::Error;
+use helpers::*;
+
+pub struct ParkinsonFactory {
+    window_size: usize,
+    periods_per_year: f64,
+}
+
+/// Parkinson volatility estimator, based on the log of the high-low range
+#[derive(Clone, Debug)]
+pub struct Parkinson {
+    periods_per_year: f64,
+    range_average: indicators::SMA,
+}
+
+impl ParkinsonFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            periods_per_year: 1.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// annualises the result by the square root of this many bars per year. Defaults to one,
+    /// which leaves it per bar
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    pub fn build(self) -> Result<Parkinson, Error> {
+        check_periods_per_year(self.periods_per_year)?;
+        Ok(Parkinson {
+            periods_per_year: self.periods_per_year,
+            range_average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<U: High + Low> Indicator<U> for Parkinson {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let range = (next.high() / next.low()).ln();
+        let variance = self.range_average.next(range * range) / (4.0 * std::f64::consts::LN_2);
+        (variance * self.periods_per_year).sqrt()
+    }
+}
+
+impl<U: High + Low> indicators::Volatility<U> for Parkinson {}
+
+impl Parkinson {
+    pub fn factory() -> ParkinsonFactory {
+        ParkinsonFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::Parkinson, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        Parkinson::factory()
+            .with_window_size(20)
+            .with_periods_per_year(252.0)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut parkinson = Parkinson::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(
+            parkinson.next(Candle::new(10.0, 10.5, 11.0, 9.0, 0.0).unwrap()),
+            0.12051503455317561
+        );
+        assert_eq!(
+            parkinson.next(Candle::new(10.5, 11.0, 12.0, 10.0, 0.0).unwrap()),
+            0.11513705776996963
+        );
+        assert_eq!(
+            parkinson.next(Candle::new(11.0, 10.4, 11.5, 10.2, 0.0).unwrap()),
+            0.10279958678910633
+        );
+        assert_eq!(
+            parkinson.next(Candle::new(10.4, 10.0, 10.8, 9.5, 0.0).unwrap()),
+            0.08777276955720069
+        );
+
+        let mut annualised = Parkinson::factory()
+            .with_window_size(3)
+            .with_periods_per_year(4.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            annualised.next(Candle::new(10.0, 10.5, 11.0, 9.0, 0.0).unwrap()),
+            2.0 * 0.12051503455317561
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::*;
+use anyhow::Error;
+use helpers::*;
+
+pub struct RogersSatchellFactory {
+    window_size: usize,
+    periods_per_year: f64,
+}
+
+/// Rogers–Satchell volatility estimator, which remains unbiased when the price drifts
+#[derive(Clone, Debug)]
+pub struct RogersSatchell {
+    periods_per_year: f64,
+    variance_average: indicators::SMA,
+}
+
+impl RogersSatchellFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            periods_per_year: 1.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// annualises the result by the square root of this many bars per year. Defaults to one,
+    /// which leaves it per bar
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    pub fn build(self) -> Result<RogersSatchell, Error> {
+        check_periods_per_year(self.periods_per_year)?;
+        Ok(RogersSatchell {
+            periods_per_year: self.periods_per_year,
+            variance_average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+pub(crate) fn rogers_satchell_term<U: Open + High + Low + Close>(next: &U) -> f64 {
+    let (open, high, low, close) = (next.open(), next.high(), next.low(), next.close());
+    (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+}
+
+impl<U: Open + High + Low + Close> Indicator<U> for RogersSatchell {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let term = rogers_satchell_term(&next);
+        (self.variance_average.next(term).max(0.0) * self.periods_per_year).sqrt()
+    }
+}
+
+impl<U: Open + High + Low + Close> indicators::Volatility<U> for RogersSatchell {}
+
+impl RogersSatchell {
+    pub fn factory() -> RogersSatchellFactory {
+        RogersSatchellFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RogersSatchell, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        RogersSatchell::factory()
+            .with_window_size(20)
+            .with_periods_per_year(252.0)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rogers_satchell = RogersSatchell::factory()
+            .with_window_size(3)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            rogers_satchell.next(Candle::new(10.0, 10.5, 11.0, 9.0, 0.0).unwrap()),
+            0.14378882491440728
+        );
+        assert_eq!(
+            rogers_satchell.next(Candle::new(10.5, 11.0, 12.0, 10.0, 0.0).unwrap()),
+            0.13591205949719798
+        );
+        assert_eq!(
+            rogers_satchell.next(Candle::new(11.0, 10.4, 11.5, 10.2, 0.0).unwrap()),
+            0.1195542036357752
+        );
+        assert_eq!(
+            rogers_satchell.next(Candle::new(10.4, 10.0, 10.8, 9.5, 0.0).unwrap()),
+            0.09958529546751181
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::*;
+use anyhow::Error;
+
+pub struct RviFactory<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    up_indicator: T,
+    down_indicator: T,
+    window_size: usize,
+}
+
+/// Relative Volatility Index: an `RSI` where the size of each up or down move is replaced by the
+/// `SD` of the closes over `window_size` bars
+#[derive(Clone, Debug)]
+pub struct RVI<T>
+where
+    T: indicators::MovingAverage<f64>,
+{
+    up_indicator: T,
+    down_indicator: T,
+    standard_deviation: indicators::SD<indicators::SMA>,
+    prev: f64,
+    is_new: bool,
+}
+
+impl<T: indicators::MovingAverage<f64>> RviFactory<T> {
+    pub fn new() -> RviFactory<indicators::EMA> {
+        RviFactory {
+            up_indicator: indicators::EMA::factory().build().unwrap(),
+            down_indicator: indicators::EMA::factory().build().unwrap(),
+            window_size: 10,
+        }
+    }
+
+    pub fn with_moving_average<U: Clone + indicators::MovingAverage<f64>>(
+        self,
+        moving_average: U,
+    ) -> RviFactory<U> {
+        RviFactory {
+            up_indicator: moving_average.clone(),
+            down_indicator: moving_average,
+            window_size: self.window_size,
+        }
+    }
+
+    /// window of the standard deviation of the closes
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<RVI<T>, Error> {
+        Ok(RVI {
+            up_indicator: self.up_indicator,
+            down_indicator: self.down_indicator,
+            standard_deviation: indicators::SD::<indicators::SMA>::factory()
+                .with_moving_average(
+                    indicators::SMA::factory()
+                        .with_window_size(self.window_size)
+                        .build()?,
+                )
+                .build()?,
+            prev: 0.0,
+            is_new: true,
+        })
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> Indicator<U> for RVI<T> {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let mut up = 0.0;
+        let mut down = 0.0;
+        let new = next.close();
+        let deviation = self.standard_deviation.next(new);
+
+        if self.is_new {
+            self.is_new = false;
+        } else if new > self.prev {
+            up = deviation;
+        } else if new < self.prev {
+            down = deviation;
+        }
+
+        self.prev = new;
+        let up_average = self.up_indicator.next(up);
+        let down_average = self.down_indicator.next(down);
+        if up_average + down_average == 0.0 {
+            return 50.0;
+        }
+        100.0 * up_average / (up_average + down_average)
+    }
+}
+
+impl<T: indicators::MovingAverage<f64>, U: Close> indicators::Volatility<U> for RVI<T> {}
+
+impl<T: indicators::MovingAverage<f64>> RVI<T> {
+    pub fn factory() -> RviFactory<indicators::EMA> {
+        RviFactory::<T>::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::RVI, indicators::SMA, Indicator};
+
+    #[test]
+    fn test_build() {
+        RVI::<SMA>::factory()
+            .with_moving_average(SMA::factory().build().unwrap())
+            .with_window_size(10)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rvi = RVI::<SMA>::factory()
+            .with_moving_average(SMA::factory().with_window_size(5).build().unwrap())
+            .with_window_size(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(rvi.next(10.0), 50.0);
+        assert_eq!(rvi.next(20.0), 100.0);
+        assert_eq!(rvi.next(15.0), 55.051025721682194);
+        assert_eq!(rvi.next(10.0), 37.797854961175304);
+        assert_eq!(rvi.next(10.0), 37.797854961175304);
+        assert_eq!(rvi.next(10.0), 37.797854961175304);
+        assert_eq!(rvi.next(12.5), 19.553660533243754);
+    }
+}
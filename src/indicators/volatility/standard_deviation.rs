@@ -1,25 +1,35 @@
-use crate::*;
+use crate::{indicators::VarianceKind, *};
 use anyhow::Error;
-use std::collections::VecDeque;
 pub struct SdFactory<T>
 where
     T: indicators::MovingAverage<f64> + Clone,
 {
     moving_average: T,
+    variance_kind: VarianceKind,
 }
 
+/// Standard deviation of the inputs, weighted the same way as the given moving average.
+/// `SMA`, `EMA` and `WMA` track their variance directly, which stays accurate for large prices
+/// with a small spread. Other averages fall back to the difference between an average of
+/// squares and the squared average, both taken relative to the first input to limit
+/// cancellation, and their sample variance is scaled by `n / (n - 1)` as for equal weights
+#[derive(Clone, Debug)]
 pub struct SD<T>
 where
     T: indicators::MovingAverage<f64> + Clone,
 {
     moving_average: T,
-    squares_average: T,
+    squares_average: Option<T>,
+    variance_kind: VarianceKind,
+    shift: Option<f64>,
+    count: usize,
 }
 
 impl<T: indicators::MovingAverage<f64> + Clone> SdFactory<T> {
     pub fn new() -> SdFactory<indicators::SMA> {
         SdFactory {
             moving_average: indicators::SMA::factory().build().unwrap(),
+            variance_kind: VarianceKind::Population,
         }
     }
 
@@ -27,13 +37,28 @@ impl<T: indicators::MovingAverage<f64> + Clone> SdFactory<T> {
         self,
         moving_average: U,
     ) -> SdFactory<U> {
-        SdFactory { moving_average }
+        SdFactory {
+            moving_average,
+            variance_kind: self.variance_kind,
+        }
+    }
+
+    pub fn with_variance_kind(mut self, variance_kind: VarianceKind) -> Self {
+        self.variance_kind = variance_kind;
+        self
     }
 
     pub fn build(self) -> Result<SD<T>, Error> {
+        let squares_average = match self.moving_average.variance(&self.variance_kind) {
+            Some(_) => None,
+            None => Some(self.moving_average.clone()),
+        };
         Ok(SD {
-            moving_average: self.moving_average.clone(),
-            squares_average: self.moving_average,
+            moving_average: self.moving_average,
+            squares_average,
+            variance_kind: self.variance_kind,
+            shift: None,
+            count: 0,
         })
     }
 }
@@ -42,10 +67,32 @@ impl<T: indicators::MovingAverage<f64> + Clone, U: Close> Indicator<U> for SD<T>
     type Output = f64;
     fn next(&mut self, next: U) -> Self::Output {
         let new = next.close();
-        let average = self.moving_average.next(new);
-        let squares_average = self.squares_average.next(new.powi(2));
+        let squares_average = match &mut self.squares_average {
+            Some(squares_average) => squares_average,
+            None => {
+                self.moving_average.next(new);
+                return self
+                    .moving_average
+                    .variance(&self.variance_kind)
+                    .unwrap_or(0.0)
+                    .sqrt();
+            }
+        };
 
-        (squares_average - average.powi(2)).abs().sqrt()
+        let shifted = new - *self.shift.get_or_insert(new);
+        let average = self.moving_average.next(shifted);
+        let squares_average = squares_average.next(shifted.powi(2));
+        let variance = (squares_average - average.powi(2)).max(0.0);
+
+        self.count = (self.count + 1).min(self.moving_average.window_size());
+        match self.variance_kind {
+            VarianceKind::Population => variance.sqrt(),
+            VarianceKind::Sample if self.count < 2 => 0.0,
+            VarianceKind::Sample => {
+                let n = self.count as f64;
+                (variance * n / (n - 1.0)).sqrt()
+            }
+        }
     }
 }
 
@@ -57,7 +104,28 @@ impl<T: indicators::MovingAverage<f64> + Clone> SD<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{indicators::SD, indicators::SMA, Indicator};
+    use crate::{
+        indicators::{MovingAverage, VarianceKind, EMA, SD, SMA, WMA},
+        Indicator,
+    };
+
+    // prices around 60 000 that move by fractions of a cent
+    fn large_offset(i: usize) -> f64 {
+        60_000.0 + ((i * 7919) % 101) as f64 * 1e-3
+    }
+
+    // two pass weighted standard deviation, with the weights in input order
+    fn exact(values: &[f64], weights: &[f64]) -> f64 {
+        let total: f64 = weights.iter().sum();
+        let mean = values.iter().zip(weights).map(|(x, w)| x * w).sum::<f64>() / total;
+        let variance = values
+            .iter()
+            .zip(weights)
+            .map(|(x, w)| w * (x - mean).powi(2))
+            .sum::<f64>()
+            / total;
+        variance.sqrt()
+    }
 
     #[test]
     fn test_build() {
@@ -76,10 +144,129 @@ mod tests {
 
         assert_eq!(sd.next(10.0), 0.0);
         assert_eq!(sd.next(20.0), 5.0);
-        assert_eq!(sd.next(15.0), 4.082482904638629);
+        assert_eq!(sd.next(15.0), 4.08248290463863);
         assert_eq!(sd.next(10.0), 4.14578098794425);
         assert_eq!(sd.next(10.0), 4.0);
         assert_eq!(sd.next(10.0), 4.0);
         assert_eq!(sd.next(12.5), 2.0);
     }
+
+    #[test]
+    fn test_sample() {
+        let mut sd = SD::<SMA>::factory()
+            .with_moving_average(SMA::factory().with_window_size(3).build().unwrap())
+            .with_variance_kind(VarianceKind::Sample)
+            .build()
+            .unwrap();
+
+        assert_eq!(sd.next(10.0), 0.0);
+        assert_eq!(sd.next(20.0), 7.0710678118654755);
+        assert_eq!(sd.next(15.0), 5.0);
+        assert_eq!(sd.next(10.0), 5.0);
+    }
+
+    #[test]
+    fn test_large_offset_sma() {
+        let mut sd = SD::<SMA>::factory()
+            .with_moving_average(SMA::factory().with_window_size(20).build().unwrap())
+            .build()
+            .unwrap();
+
+        let values: Vec<f64> = (0..100_000).map(large_offset).collect();
+        for (i, value) in values.iter().enumerate() {
+            let result = sd.next(*value);
+            if i >= 19 && i % 1000 == 0 {
+                let expected = exact(&values[i - 19..=i], &[1.0; 20]);
+                assert!((result - expected).abs() < expected * 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_offset_ema() {
+        let mut sd = SD::<EMA>::factory()
+            .with_moving_average(EMA::factory().with_window_size(9).build().unwrap())
+            .build()
+            .unwrap();
+
+        let k = 0.2;
+        let values: Vec<f64> = (0..500).map(large_offset).collect();
+        for (i, value) in values.iter().enumerate() {
+            let result = sd.next(*value);
+            let weights: Vec<f64> = (0..=i)
+                .map(|j| match j {
+                    0 => (1.0_f64 - k).powi(i as i32),
+                    _ => k * (1.0_f64 - k).powi((i - j) as i32),
+                })
+                .collect();
+            let expected = exact(&values[..=i], &weights);
+            assert!((result - expected).abs() <= expected * 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_large_offset_wma() {
+        let mut sd = SD::<WMA>::factory()
+            .with_moving_average(WMA::factory().with_window_size(5).build().unwrap())
+            .build()
+            .unwrap();
+
+        let values: Vec<f64> = (0..1000).map(large_offset).collect();
+        for (i, value) in values.iter().enumerate() {
+            let result = sd.next(*value);
+            if i >= 4 {
+                let expected = exact(&values[i - 4..=i], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+                assert!((result - expected).abs() < expected * 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_wma() {
+        let mut sd = SD::<WMA>::factory()
+            .with_moving_average(WMA::factory().with_window_size(3).build().unwrap())
+            .with_variance_kind(VarianceKind::Sample)
+            .build()
+            .unwrap();
+
+        // weights 1, 2 and 3 have Σw² / (Σw)² = 14 / 36
+        let population = exact(&[10.0, 20.0, 15.0], &[1.0, 2.0, 3.0]);
+        assert_eq!(sd.next(10.0), 0.0);
+        sd.next(20.0);
+        assert!((sd.next(15.0) - population / (1.0 - 14.0 / 36.0_f64).sqrt()).abs() < 1e-12);
+    }
+
+    // a `WMA` that doesn't report its variance, to exercise the fallback
+    #[derive(Clone, Debug)]
+    struct Untracked(WMA);
+
+    impl Indicator<f64> for Untracked {
+        type Output = f64;
+        fn next(&mut self, next: f64) -> Self::Output {
+            self.0.next(next)
+        }
+    }
+
+    impl MovingAverage<f64> for Untracked {
+        fn window_size(&self) -> usize {
+            MovingAverage::<f64>::window_size(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_large_offset_fallback() {
+        let mut sd = SD::<Untracked>::factory()
+            .with_moving_average(Untracked(WMA::factory().with_window_size(5).build().unwrap()))
+            .build()
+            .unwrap();
+
+        let values: Vec<f64> = (0..1000).map(large_offset).collect();
+        for (i, value) in values.iter().enumerate() {
+            let result = sd.next(*value);
+            if i >= 4 {
+                let expected = exact(&values[i - 4..=i], &[1.0, 2.0, 3.0, 4.0, 5.0]);
+                assert!((result - expected).abs() < expected * 1e-6);
+            }
+        }
+    }
 }
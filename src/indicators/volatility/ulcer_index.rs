@@ -0,0 +1,77 @@
+use crate::*;
+use anyhow::Error;
+
+pub struct UlcerIndexFactory {
+    window_size: usize,
+}
+
+/// Ulcer Index: the root mean square of the percentage drawdowns from the highest close of the
+/// window
+#[derive(Clone, Debug)]
+pub struct UlcerIndex {
+    max: indicators::RollingMax,
+    drawdown_average: indicators::SMA,
+}
+
+impl UlcerIndexFactory {
+    pub fn new() -> Self {
+        Self { window_size: 14 }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    pub fn build(self) -> Result<UlcerIndex, Error> {
+        Ok(UlcerIndex {
+            max: indicators::RollingMax::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+            drawdown_average: indicators::SMA::factory()
+                .with_window_size(self.window_size)
+                .build()?,
+        })
+    }
+}
+
+impl<U: Close> Indicator<U> for UlcerIndex {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        let new = next.close();
+        let max = self.max.next(new);
+        let drawdown = (new - max) / max * 100.0;
+        self.drawdown_average.next(drawdown * drawdown).sqrt()
+    }
+}
+
+impl<U: Close> indicators::Volatility<U> for UlcerIndex {}
+
+impl UlcerIndex {
+    pub fn factory() -> UlcerIndexFactory {
+        UlcerIndexFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::UlcerIndex, Indicator};
+
+    #[test]
+    fn test_build() {
+        UlcerIndex::factory().with_window_size(14).build().unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ulcer_index = UlcerIndex::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(ulcer_index.next(10.0), 0.0);
+        assert_eq!(ulcer_index.next(20.0), 0.0);
+        assert_eq!(ulcer_index.next(15.0), 14.433756729740644);
+        assert_eq!(ulcer_index.next(10.0), 32.274861218395145);
+        assert_eq!(ulcer_index.next(10.0), 37.57708127352412);
+        assert_eq!(ulcer_index.next(10.0), 34.69443332443554);
+        assert_eq!(ulcer_index.next(12.5), 19.245008972987524);
+    }
+}
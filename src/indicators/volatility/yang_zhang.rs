@@ -0,0 +1,125 @@
+use crate::{
+    indicators::{
+        volatility::rogers_satchell::rogers_satchell_term, MovingAverage, VarianceKind, SMA,
+    },
+    *,
+};
+use anyhow::Error;
+use helpers::*;
+
+pub struct YangZhangFactory {
+    window_size: usize,
+    periods_per_year: f64,
+}
+
+/// Yang–Zhang volatility estimator: a weighted sum of the overnight (close to open) variance, the
+/// open to close variance and the Rogers–Satchell variance. The first bar has no previous close,
+/// so its overnight return is taken to be zero
+#[derive(Clone, Debug)]
+pub struct YangZhang {
+    periods_per_year: f64,
+    k: f64,
+    prev_close: Option<f64>,
+    overnight: SMA,
+    body: SMA,
+    rogers_satchell: SMA,
+}
+
+impl YangZhangFactory {
+    pub fn new() -> Self {
+        Self {
+            window_size: 14,
+            periods_per_year: 1.0,
+        }
+    }
+
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// annualises the result by the square root of this many bars per year. Defaults to one,
+    /// which leaves it per bar
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    pub fn build(self) -> Result<YangZhang, Error> {
+        check_window_size(self.window_size)?;
+        check_periods_per_year(self.periods_per_year)?;
+        let n = self.window_size as f64;
+        Ok(YangZhang {
+            periods_per_year: self.periods_per_year,
+            k: 0.34 / (1.34 + (n + 1.0) / (n - 1.0)),
+            prev_close: None,
+            overnight: SMA::factory().with_window_size(self.window_size).build()?,
+            body: SMA::factory().with_window_size(self.window_size).build()?,
+            rogers_satchell: SMA::factory().with_window_size(self.window_size).build()?,
+        })
+    }
+}
+
+fn sample_variance(returns: &SMA) -> f64 {
+    MovingAverage::<f64>::variance(returns, &VarianceKind::Sample).unwrap_or(0.0)
+}
+
+impl<U: Open + High + Low + Close> Indicator<U> for YangZhang {
+    type Output = f64;
+    fn next(&mut self, next: U) -> Self::Output {
+        self.overnight
+            .next((next.open() / self.prev_close.unwrap_or(next.open())).ln());
+        self.body.next((next.close() / next.open()).ln());
+        let rogers_satchell = self.rogers_satchell.next(rogers_satchell_term(&next));
+        self.prev_close = Some(next.close());
+
+        let variance = sample_variance(&self.overnight)
+            + self.k * sample_variance(&self.body)
+            + (1.0 - self.k) * rogers_satchell.max(0.0);
+        (variance * self.periods_per_year).sqrt()
+    }
+}
+
+impl<U: Open + High + Low + Close> indicators::Volatility<U> for YangZhang {}
+
+impl YangZhang {
+    pub fn factory() -> YangZhangFactory {
+        YangZhangFactory::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indicators::YangZhang, Candle, Indicator};
+
+    #[test]
+    fn test_build() {
+        YangZhang::factory()
+            .with_window_size(20)
+            .with_periods_per_year(252.0)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_next() {
+        let mut yang_zhang = YangZhang::factory().with_window_size(3).build().unwrap();
+
+        assert_eq!(
+            yang_zhang.next(Candle::new(10.0, 10.5, 11.0, 9.0, 0.0).unwrap()),
+            0.13627385086062072
+        );
+        assert_eq!(
+            yang_zhang.next(Candle::new(10.5, 11.0, 12.0, 10.0, 0.0).unwrap()),
+            0.12880977463416002
+        );
+        assert_eq!(
+            yang_zhang.next(Candle::new(11.0, 10.4, 11.5, 10.2, 0.0).unwrap()),
+            0.11490670512705681
+        );
+        assert_eq!(
+            yang_zhang.next(Candle::new(10.4, 10.0, 10.8, 9.5, 0.0).unwrap()),
+            0.09599935769730698
+        );
+    }
+}
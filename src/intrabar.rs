@@ -0,0 +1,117 @@
+use crate::indicator::*;
+
+/// Wraps an indicator so its most recent input can be replaced, for feeding a bar repeatedly
+/// while it forms. `next` commits a new input, and `update_last` recomputes the output as if the
+/// latest input had been the given one.
+///
+/// Revising needs the state from before the latest input, so every `next` clones the wrapped
+/// indicator and every `update_last` clones that saved state. Each call therefore costs a copy
+/// of the whole state, such as every window a moving average keeps, whether or not the bar is
+/// ever revised. Feed the indicator directly when bars are final, and use `Peek` to look at a
+/// forming bar now and then without keeping a copy
+#[derive(Clone, Debug)]
+pub struct Intrabar<I> {
+    indicator: I,
+    previous: Option<I>,
+}
+
+impl<I: Clone> Intrabar<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            previous: None,
+        }
+    }
+
+    /// replaces the latest input with `last`, or commits it if there were no inputs yet
+    pub fn update_last<T>(&mut self, last: T) -> I::Output
+    where
+        I: Indicator<T>,
+    {
+        match &self.previous {
+            Some(previous) => {
+                self.indicator = previous.clone();
+                self.indicator.next(last)
+            }
+            None => self.next(last),
+        }
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+}
+
+impl<T, I: Indicator<T> + Clone> Indicator<T> for Intrabar<I> {
+    type Output = I::Output;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.previous = Some(self.indicator.clone());
+        self.indicator.next(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{BollingerBand, EMA, MACD, PSAR, SMA},
+        *,
+    };
+
+    #[test]
+    fn test_peek() {
+        let mut ema = EMA::factory().with_window_size(3).build().unwrap();
+        let mut reference = ema.clone();
+        for x in [10.0, 20.0, 15.0] {
+            assert_eq!(ema.peek(x + 100.0), ema.clone().next(x + 100.0));
+            assert_eq!(ema.next(x), reference.next(x));
+        }
+
+        let mut psar = PSAR::factory().build().unwrap();
+        let mut reference = psar.clone();
+        for (high, low) in [
+            (11.0, 9.0),
+            (12.0, 10.0),
+            (13.0, 11.0),
+            (12.5, 10.0),
+            (11.0, 8.0),
+        ] {
+            let candle = Candle::new(low, high, high, low, 0.0).unwrap();
+            psar.peek(Candle::new(1.0, 50.0, 50.0, 1.0, 0.0).unwrap());
+            assert_eq!(psar.next(candle.clone()), reference.next(candle));
+        }
+    }
+
+    #[test]
+    fn test_update_last() {
+        let closes = [10.0, 20.0, 15.0, 10.0, 12.5];
+        // each bar is first seen at 11.0 and then revised to its close
+        let mut sma = Intrabar::new(SMA::factory().with_window_size(3).build().unwrap());
+        let mut macd = Intrabar::new(MACD::<EMA, EMA, EMA>::factory().build().unwrap());
+        let mut bollinger_band = Intrabar::new(BollingerBand::<SMA>::factory().build().unwrap());
+        let mut reference_sma = SMA::factory().with_window_size(3).build().unwrap();
+        let mut reference_macd = MACD::<EMA, EMA, EMA>::factory().build().unwrap();
+        let mut reference_bollinger_band = BollingerBand::<SMA>::factory().build().unwrap();
+
+        for close in closes {
+            sma.next(11.0);
+            macd.next(11.0);
+            bollinger_band.next(11.0);
+            let revised = sma.update_last(close + 1.0);
+            assert_eq!(sma.update_last(close + 1.0), revised);
+            assert_eq!(sma.update_last(close), reference_sma.next(close));
+            assert_eq!(macd.update_last(close), reference_macd.next(close));
+            assert_eq!(
+                bollinger_band.update_last(close),
+                reference_bollinger_band.next(close)
+            );
+        }
+
+        let mut first = Intrabar::new(SMA::factory().with_window_size(3).build().unwrap());
+        assert_eq!(first.update_last(10.0), 10.0);
+        assert_eq!(first.next(20.0), 15.0);
+    }
+}
@@ -32,6 +32,30 @@
 //! let mut my_indicator = MyIndicator{};
 //! assert_eq!(my_indicator.next(10.0), 10.0);
 //!```
+//!
+//! With the `derive` feature, your own bar types can implement the accessor traits with
+//! `#[derive(Ohlcv)]`, marking fields whose names differ with `#[ta(...)]`:
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use technical_analysis::{indicators::TR, Indicator, Ohlcv};
+//!
+//! #[derive(Ohlcv)]
+//! struct Bar {
+//!     #[ta(high)]
+//!     h: f64,
+//!     #[ta(low)]
+//!     l: f64,
+//!     close: f64,
+//! }
+//!
+//! let mut tr = TR::factory().build().unwrap();
+//! assert_eq!(tr.next(Bar { h: 12.0, l: 9.0, close: 10.0 }), 3.0);
+//! ```
+
+// the tests name their input types after the fields they carry, such as `CHL`
+#![cfg_attr(test, allow(clippy::upper_case_acronyms))]
+
+pub mod bars;
 
 mod candle;
 pub use candle::Candle;
@@ -43,6 +67,15 @@ pub use indicator::*;
 
 pub mod indicators;
 
+mod intrabar;
+pub use intrabar::Intrabar;
+
+#[cfg(feature = "derive")]
+pub use technical_analysis_derive::Ohlcv;
+
+mod price_source;
+pub use price_source::{PriceSource, Sourced};
+
 #[cfg(test)]
 mod tests {
 
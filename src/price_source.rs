@@ -0,0 +1,165 @@
+use crate::indicator::*;
+
+/// which price of a bar a single series indicator reads
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PriceSource {
+    Open,
+    High,
+    Low,
+    Close,
+    /// the median price, `(high + low) / 2`
+    Hl2,
+    /// the typical price, `(high + low + close) / 3`
+    Hlc3,
+    /// `(open + high + low + close) / 4`
+    Ohlc4,
+    /// the weighted close, `(high + low + 2 * close) / 4`
+    WeightedClose,
+}
+
+impl PriceSource {
+    pub fn price<T: Open + High + Low + Close>(&self, bar: &T) -> f64 {
+        match self {
+            PriceSource::Open => bar.open(),
+            PriceSource::High => bar.high(),
+            PriceSource::Low => bar.low(),
+            PriceSource::Close => bar.close(),
+            PriceSource::Hl2 => (bar.high() + bar.low()) / 2.0,
+            PriceSource::Hlc3 => (bar.high() + bar.low() + bar.close()) / 3.0,
+            PriceSource::Ohlc4 => (bar.open() + bar.high() + bar.low() + bar.close()) / 4.0,
+            PriceSource::WeightedClose => (bar.high() + bar.low() + 2.0 * bar.close()) / 4.0,
+        }
+    }
+}
+
+/// Input adaptor that feeds an indicator over `f64` with the chosen price of each bar, so any
+/// single series indicator can run on bars:
+/// ```
+/// use technical_analysis::{
+///     indicators::{BollingerBand, SMA},
+///     Candle, Indicator, PriceSource, Sourced,
+/// };
+///
+/// let bollinger_band = BollingerBand::<SMA>::factory().build().unwrap();
+/// let mut typical = Sourced::new(PriceSource::Hlc3, bollinger_band);
+/// let band = typical.next(Candle::new(10.0, 12.0, 13.0, 8.0, 100.0).unwrap());
+/// assert_eq!(band.middle, 11.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sourced<I> {
+    source: PriceSource,
+    indicator: I,
+}
+
+impl<I> Sourced<I> {
+    pub fn new(source: PriceSource, indicator: I) -> Self {
+        Self { source, indicator }
+    }
+
+    pub fn source(&self) -> PriceSource {
+        self.source
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn into_inner(self) -> I {
+        self.indicator
+    }
+}
+
+impl<T: Open + High + Low + Close, I: Indicator<f64>> Indicator<T> for Sourced<I> {
+    type Output = I::Output;
+    fn next(&mut self, next: T) -> Self::Output {
+        self.indicator.next(self.source.price(&next))
+    }
+}
+
+impl<T: Open + High + Low + Close, M: crate::indicators::MovingAverage<f64>>
+    crate::indicators::MovingAverage<T> for Sourced<M>
+{
+    fn window_size(&self) -> usize {
+        self.indicator.window_size()
+    }
+
+    fn variance(&self, kind: &crate::indicators::VarianceKind) -> Option<f64> {
+        self.indicator.variance(kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        indicators::{BollingerBand, EMA, MACD, RSI, SD, SMA},
+        *,
+    };
+
+    #[test]
+    fn test_price() {
+        let candle = Candle::new(10.0, 12.0, 14.0, 8.0, 100.0).unwrap();
+        assert_eq!(PriceSource::Open.price(&candle), 10.0);
+        assert_eq!(PriceSource::High.price(&candle), 14.0);
+        assert_eq!(PriceSource::Low.price(&candle), 8.0);
+        assert_eq!(PriceSource::Close.price(&candle), 12.0);
+        assert_eq!(PriceSource::Hl2.price(&candle), 11.0);
+        assert_eq!(PriceSource::Hlc3.price(&candle), 34.0 / 3.0);
+        assert_eq!(PriceSource::Ohlc4.price(&candle), 11.0);
+        assert_eq!(PriceSource::WeightedClose.price(&candle), 11.5);
+    }
+
+    #[test]
+    fn test_next() {
+        let candles = [
+            Candle::new(10.0, 12.0, 14.0, 8.0, 100.0).unwrap(),
+            Candle::new(12.0, 15.0, 16.0, 11.0, 100.0).unwrap(),
+            Candle::new(15.0, 11.0, 15.5, 10.0, 100.0).unwrap(),
+            Candle::new(11.0, 13.0, 13.5, 10.5, 100.0).unwrap(),
+        ];
+
+        let mut sourced_sma = Sourced::new(
+            PriceSource::Hl2,
+            SMA::factory().with_window_size(3).build().unwrap(),
+        );
+        let mut sourced_ema = Sourced::new(
+            PriceSource::Ohlc4,
+            EMA::factory().with_window_size(3).build().unwrap(),
+        );
+        let mut sourced_rsi =
+            Sourced::new(PriceSource::Low, RSI::<EMA>::factory().build().unwrap());
+        let mut sourced_bollinger_band = Sourced::new(
+            PriceSource::Hlc3,
+            BollingerBand::<SMA>::factory().build().unwrap(),
+        );
+        let mut sourced_sd = Sourced::new(
+            PriceSource::WeightedClose,
+            SD::<SMA>::factory().build().unwrap(),
+        );
+        let mut sourced_macd = Sourced::new(
+            PriceSource::Close,
+            MACD::<EMA, EMA, EMA>::factory().build().unwrap(),
+        );
+        let mut sma = SMA::factory().with_window_size(3).build().unwrap();
+        let mut ema = EMA::factory().with_window_size(3).build().unwrap();
+        let mut rsi = RSI::<EMA>::factory().build().unwrap();
+        let mut bollinger_band = BollingerBand::<SMA>::factory().build().unwrap();
+        let mut sd = SD::<SMA>::factory().build().unwrap();
+        let mut macd = MACD::<EMA, EMA, EMA>::factory().build().unwrap();
+
+        for candle in candles {
+            let hl2 = (candle.high + candle.low) / 2.0;
+            let ohlc4 = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+            let hlc3 = (candle.high + candle.low + candle.close) / 3.0;
+            let weighted_close = (candle.high + candle.low + 2.0 * candle.close) / 4.0;
+            assert_eq!(sourced_sd.next(candle.clone()), sd.next(weighted_close));
+            assert_eq!(sourced_macd.next(candle.clone()), macd.next(candle.close));
+            assert_eq!(sourced_sma.next(candle.clone()), sma.next(hl2));
+            assert_eq!(sourced_ema.next(candle.clone()), ema.next(ohlc4));
+            assert_eq!(sourced_rsi.next(candle.clone()), rsi.next(candle.low));
+            assert_eq!(
+                sourced_bollinger_band.next(candle),
+                bollinger_band.next(hlc3)
+            );
+        }
+    }
+}
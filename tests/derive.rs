@@ -0,0 +1,77 @@
+#![cfg(feature = "derive")]
+
+use technical_analysis::{indicators::TR, *};
+
+mod reexport {
+    pub use technical_analysis as ta;
+}
+
+#[derive(Clone, Debug, Ohlcv)]
+struct Bar {
+    #[ta(timestamp)]
+    time: i64,
+    #[ta(open)]
+    first: f32,
+    #[ta(high)]
+    h: f64,
+    #[ta(low)]
+    l: f64,
+    close: f64,
+    #[ta(volume)]
+    size: u32,
+}
+
+#[derive(Clone, Debug, Ohlcv)]
+struct Chl<T: Copy + Into<f64>> {
+    close: f64,
+    high: f64,
+    low: f64,
+    // marked fields are never matched by name
+    #[ta(volume)]
+    open: u32,
+    tag: T,
+}
+
+#[test]
+fn test_marked_fields() {
+    let bar = Bar {
+        time: 1_700_000_000,
+        first: 10.5,
+        h: 12.0,
+        l: 9.0,
+        close: 11.0,
+        size: 300,
+    };
+    assert_eq!(bar.timestamp(), 1_700_000_000);
+    assert_eq!(bar.open(), 10.5);
+    assert_eq!(bar.high(), 12.0);
+    assert_eq!(bar.low(), 9.0);
+    assert_eq!(bar.close(), 11.0);
+    assert_eq!(bar.volume(), 300.0);
+}
+
+#[test]
+fn test_named_fields() {
+    let mut tr = TR::factory().build().unwrap();
+    let bar = Chl {
+        close: 15.0,
+        high: 20.0,
+        low: 10.0,
+        open: 7,
+        tag: 1.0_f32,
+    };
+    assert_eq!(bar.volume(), 7.0);
+    assert_eq!(Into::<f64>::into(bar.tag), 1.0);
+    assert_eq!(tr.next(bar), 10.0);
+}
+
+#[derive(Clone, Debug, Ohlcv)]
+#[ta(crate = crate::reexport::ta)]
+struct Reexported {
+    close: f32,
+}
+
+#[test]
+fn test_crate_path() {
+    assert_eq!(Reexported { close: 2.5 }.close(), 2.5);
+}